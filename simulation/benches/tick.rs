@@ -0,0 +1,77 @@
+//! Compares the serial and rayon paths of `Simulation::tick` at `max_creatures`,
+//! and reports how many heap allocations one such tick makes.
+//!
+//! Usage: cargo bench -p evolution-sim --features parallel --bench tick
+//!
+//! Without the `parallel` feature both cases run the serial path.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use evolution_sim::simulation::{SimConfig, Simulation};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Forwards to the system allocator, counting every allocation.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// A simulation seeded at the population cap, so each tick does the most work.
+fn full_simulation(parallel: bool) -> Simulation {
+    let defaults = SimConfig::default();
+    let species = defaults.initial_herbivore_species + defaults.initial_carnivore_species;
+    let config = SimConfig {
+        parallel,
+        creatures_per_species: defaults.max_creatures as u32 / species,
+        ..defaults
+    };
+    Simulation::new(1, config)
+}
+
+/// Heap allocations made by a single tick of `sim`.
+fn tick_allocations(sim: &Simulation) -> usize {
+    let mut sim = sim.clone();
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+    sim.tick();
+    ALLOCATIONS.load(Ordering::Relaxed) - start
+}
+
+fn tick_at_max_population(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick_at_max_population");
+    group.sample_size(20);
+    for (name, parallel) in [("serial", false), ("parallel", true)] {
+        let sim = full_simulation(parallel);
+        println!(
+            "tick_at_max_population/{}: {} allocations per tick",
+            name,
+            tick_allocations(&sim)
+        );
+        group.bench_function(name, |b| {
+            b.iter_batched(|| sim.clone(), |mut sim| sim.tick(), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tick_at_max_population);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+//! Runs the evolution simulation natively without a browser.
+//!
+//! Usage: headless [--width N] [--height N] [--seed N] [--ticks N] [--out PATH]
+//!
+//! Writes a JSON object with the final `stats` and the population `history`
+//! to `PATH`, or to stdout when `--out` is omitted.
+
+use evolution_sim::simulation::{SimConfig, Simulation};
+use std::{env, fs, process};
+
+const USAGE: &str = "Usage: headless [--width N] [--height N] [--seed N] [--ticks N] [--out PATH]";
+
+struct Args {
+    width: u32,
+    height: u32,
+    seed: u32,
+    ticks: u64,
+    out: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let defaults = SimConfig::default();
+    let mut args = Args {
+        width: defaults.world_width,
+        height: defaults.world_height,
+        seed: 0,
+        ticks: 1000,
+        out: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            process::exit(0);
+        }
+
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let invalid = |_| format!("invalid value for {}: {}", flag, value);
+
+        match flag.as_str() {
+            "--width" => args.width = value.parse().map_err(invalid)?,
+            "--height" => args.height = value.parse().map_err(invalid)?,
+            "--seed" => args.seed = value.parse().map_err(invalid)?,
+            "--ticks" => args.ticks = value.parse().map_err(invalid)?,
+            "--out" => args.out = Some(value),
+            _ => return Err(format!("unknown flag {}", flag)),
+        }
+    }
+
+    Ok(args)
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let config = SimConfig {
+        world_width: args.width,
+        world_height: args.height,
+        ..SimConfig::default()
+    };
+    let mut sim = Simulation::try_new(args.seed, config).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2);
+    });
+    for _ in 0..args.ticks {
+        sim.tick();
+    }
+
+    let output = format!(
+        "{{\"stats\":{},\"history\":{}}}",
+        sim.get_stats_json(),
+        sim.get_history_json()
+    );
+
+    match args.out {
+        Some(path) => {
+            if let Err(err) = fs::write(&path, output) {
+                eprintln!("Failed to write {}: {}", path, err);
+                process::exit(1);
+            }
+        },
+        None => println!("{}", output),
+    }
+}
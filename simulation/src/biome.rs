@@ -37,6 +37,20 @@ impl BiomeType {
         }
     }
 
+    /// Parse a biome from its display name or variant name, ignoring case and spaces.
+    pub fn from_name(name: &str) -> Option<BiomeType> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+
+        BiomeType::ALL
+            .iter()
+            .find(|bt| bt.name().replace(' ', "").to_lowercase() == normalized)
+            .copied()
+    }
+
     pub fn color(&self) -> [u8; 3] {
         match self {
             BiomeType::IceCap => [255, 255, 255],
@@ -1,4 +1,7 @@
-use crate::genetics::{Genome, Phenotype};
+use crate::{
+    genetics::{Genome, Phenotype},
+    world::World,
+};
 use rand::{rngs::SmallRng, Rng};
 use serde::{Deserialize, Serialize};
 
@@ -53,6 +56,9 @@ pub struct Creature {
 }
 
 impl Creature {
+    /// Candidate tiles a climate-stressed creature considers when choosing a target.
+    const CLIMATE_SAMPLES: usize = 6;
+
     pub fn new(
         id: CreatureId,
         species_id: u32,
@@ -89,7 +95,8 @@ impl Creature {
     }
 
     /// Update creature for one tick. Returns energy change.
-    pub fn tick_metabolism(&mut self) -> f32 {
+    /// `carnivore_multiplier` scales the metabolic cost of carnivores.
+    pub fn tick_metabolism(&mut self, carnivore_multiplier: f32) -> f32 {
         if !self.alive {
             return 0.0;
         }
@@ -97,7 +104,10 @@ impl Creature {
         self.age += 1;
 
         // Base metabolism cost
-        let cost = self.phenotype.base_energy_cost();
+        let mut cost = self.phenotype.base_energy_cost();
+        if self.phenotype.is_carnivore() {
+            cost *= carnivore_multiplier;
+        }
         self.energy -= cost;
 
         // Reduce reproduction cooldown
@@ -162,7 +172,8 @@ impl Creature {
     }
 
     /// Eat plant biomass. Returns amount consumed.
-    pub fn eat_plants(&mut self, available: f32) -> f32 {
+    /// `energy_density` is the energy gained per unit of digested biomass.
+    pub fn eat_plants(&mut self, available: f32, energy_density: f32) -> f32 {
         if !self.alive || self.phenotype.is_carnivore() {
             return 0.0;
         }
@@ -171,14 +182,20 @@ impl Creature {
         let max_intake = self.phenotype.body_size * 3.0 * herbivore_efficiency;
         let consumed = available.min(max_intake);
 
-        self.energy += consumed * herbivore_efficiency;
+        self.energy += consumed * herbivore_efficiency * energy_density;
         self.activity = Activity::Eating;
 
         consumed
     }
 
     /// Attempt to hunt and eat another creature.
-    pub fn hunt(&mut self, prey: &mut Creature, rng: &mut SmallRng) -> bool {
+    /// `success_multiplier` scales the hunter's chance of winning the fight.
+    pub fn hunt(
+        &mut self,
+        prey: &mut Creature,
+        success_multiplier: f32,
+        rng: &mut SmallRng,
+    ) -> bool {
         if !self.alive || !prey.alive {
             return false;
         }
@@ -198,7 +215,7 @@ impl Creature {
         let hunter_power = self.phenotype.combat_power();
         let prey_power = prey.phenotype.combat_power() * 0.6; // Defender disadvantage
 
-        let success_chance = hunter_power / (hunter_power + prey_power);
+        let success_chance = hunter_power / (hunter_power + prey_power) * success_multiplier;
         if rng.gen_bool(success_chance.clamp(0.05, 0.95) as f64) {
             // Successful hunt
             let food = prey.phenotype.food_value() * self.phenotype.diet;
@@ -221,10 +238,20 @@ impl Creature {
         }
     }
 
+    /// How appealing this creature is as a mate for `chooser`. Well-fed, healthy
+    /// partners of similar body size score highest.
+    pub fn mate_score(&self, chooser: &Creature) -> f32 {
+        let a = self.phenotype.body_size;
+        let b = chooser.phenotype.body_size;
+        let size_ratio = a.min(b) / a.max(b);
+        self.energy.max(0.0) * self.health.max(0.0) * size_ratio
+    }
+
     /// Check if the creature can reproduce.
-    pub fn can_reproduce(&self) -> bool {
+    /// `threshold_multiplier` scales the energy needed above the fertility threshold.
+    pub fn can_reproduce(&self, threshold_multiplier: f32) -> bool {
         self.alive
-            && self.energy > self.phenotype.fertility_threshold
+            && self.energy > self.phenotype.fertility_threshold * threshold_multiplier
             && self.reproduction_cooldown == 0
             && self.age > 10
     }
@@ -234,21 +261,30 @@ impl Creature {
         &mut self,
         partner: &mut Creature,
         next_id: CreatureId,
+        threshold_multiplier: f32,
+        mutation_rate: f32,
+        mutation_magnitude: f32,
         rng: &mut SmallRng,
     ) -> Vec<Creature> {
-        if !self.can_reproduce() || !partner.can_reproduce() {
+        if !self.can_reproduce(threshold_multiplier) || !partner.can_reproduce(threshold_multiplier)
+        {
             return Vec::new();
         }
 
-        let offspring_count = ((self.phenotype.offspring_count + partner.phenotype.offspring_count) / 2)
-            .max(1)
-            .min(4);
+        let offspring_count =
+            ((self.phenotype.offspring_count + partner.phenotype.offspring_count) / 2).clamp(1, 4);
 
         let energy_per_offspring = self.phenotype.fertility_threshold * 0.3;
 
         let mut offspring = Vec::new();
         for i in 0..offspring_count {
-            let child_genome = Genome::crossover(&self.genome, &partner.genome, rng);
+            let child_genome = Genome::crossover(
+                &self.genome,
+                &partner.genome,
+                mutation_rate,
+                mutation_magnitude,
+                rng,
+            );
             let child = Creature::new(
                 next_id + i as u64,
                 self.species_id,
@@ -278,24 +314,64 @@ impl Creature {
         offspring
     }
 
+    /// How far a tile's climate falls outside this creature's tolerances.
+    /// Uses the same scaling as the temperature and drought stress penalties.
+    pub fn climate_stress(&self, temperature: f32, rainfall: f32) -> f32 {
+        let cold = (self.phenotype.cold_tolerance - temperature).max(0.0) / 20.0;
+        let heat = (temperature - self.phenotype.heat_tolerance).max(0.0) / 20.0;
+        let min_rainfall = (1.0 - self.phenotype.drought_tolerance) * 500.0;
+        let drought = (min_rainfall - rainfall).max(0.0) / 500.0;
+        cold + heat + drought
+    }
+
     /// Choose movement direction based on surroundings.
-    pub fn choose_direction(
-        &mut self,
-        world_width: usize,
-        world_height: usize,
-        rng: &mut SmallRng,
-    ) {
+    ///
+    /// Creatures that are comfortable wander randomly within sense range.
+    /// Stressed creatures sample several tiles in range and head for the
+    /// habitable one whose climate suits them best.
+    pub fn choose_direction(&mut self, world: &World, rng: &mut SmallRng) {
         if !self.alive {
             return;
         }
 
-        // Random wandering within sense range
+        let world_width = world.width as usize;
+        let world_height = world.height as usize;
         let range = self.phenotype.sense_range as i32;
-        let dx = rng.gen_range(-range..=range);
-        let dy = rng.gen_range(-range..=range);
+        let random_target = |x: usize, y: usize, rng: &mut SmallRng| {
+            let dx = rng.gen_range(-range..=range);
+            let dy = rng.gen_range(-range..=range);
+            let new_x = ((x as i32 + dx).rem_euclid(world_width as i32)) as usize;
+            let new_y = (y as i32 + dy).clamp(0, world_height as i32 - 1) as usize;
+            (new_x, new_y)
+        };
+
+        let here = &world.terrain[self.y][self.x];
+        let current_stress = self.climate_stress(here.temperature, here.rainfall);
+
+        let (new_x, new_y) = if current_stress > 0.0 {
+            let mut best = (self.x, self.y);
+            let mut best_stress = current_stress;
+            for _ in 0..Self::CLIMATE_SAMPLES {
+                let (cx, cy) = random_target(self.x, self.y, rng);
+                let cell = &world.terrain[cy][cx];
+                if !cell.dominant_biome().is_habitable() {
+                    continue;
+                }
+                let stress = self.climate_stress(cell.temperature, cell.rainfall);
+                if stress < best_stress {
+                    best = (cx, cy);
+                    best_stress = stress;
+                }
+            }
 
-        let new_x = ((self.x as i32 + dx).rem_euclid(world_width as i32)) as usize;
-        let new_y = (self.y as i32 + dy).clamp(0, world_height as i32 - 1) as usize;
+            if best == (self.x, self.y) {
+                random_target(self.x, self.y, rng)
+            } else {
+                best
+            }
+        } else {
+            random_target(self.x, self.y, rng)
+        };
 
         self.target_x = Some(new_x);
         self.target_y = Some(new_y);
@@ -303,7 +379,13 @@ impl Creature {
     }
 
     /// Move one step towards target.
-    pub fn move_towards_target(&mut self, world_width: usize, world_height: usize) {
+    /// `carnivore_multiplier` scales the movement cost of carnivores.
+    pub fn move_towards_target(
+        &mut self,
+        world_width: usize,
+        world_height: usize,
+        carnivore_multiplier: f32,
+    ) {
         if !self.alive {
             return;
         }
@@ -336,7 +418,11 @@ impl Creature {
         self.y = (self.y as i32 + step_y).clamp(0, world_height as i32 - 1) as usize;
 
         // Energy cost for movement
-        self.energy -= self.phenotype.movement_energy_cost();
+        let mut cost = self.phenotype.movement_energy_cost();
+        if self.phenotype.is_carnivore() {
+            cost *= carnivore_multiplier;
+        }
+        self.energy -= cost;
 
         // Clear target if reached
         if self.x == tx && self.y == ty {
@@ -346,3 +432,46 @@ impl Creature {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn heat_stressed_herbivore_moves_to_cooler_tiles() {
+        let world = World::generate(120, 60, 1);
+        let (x, y) = (0..60)
+            .flat_map(|y| (0..120).map(move |x| (x, y)))
+            .filter(|&(x, y)| world.terrain[y][x].dominant_biome().is_habitable())
+            .max_by(|a, b| {
+                let ta = world.terrain[a.1][a.0].temperature;
+                let tb = world.terrain[b.1][b.0].temperature;
+                ta.total_cmp(&tb)
+            })
+            .unwrap();
+        let start_temperature = world.terrain[y][x].temperature;
+
+        let mut rng = SmallRng::seed_from_u64(1);
+        let genome = Genome::random_herbivore(&mut rng);
+        let mut creature = Creature::new(0, 0, genome, x, y, 0, &mut rng);
+        creature.phenotype.cold_tolerance = -100.0;
+        creature.phenotype.heat_tolerance = start_temperature - 20.0;
+        creature.phenotype.drought_tolerance = 1.0;
+
+        for _ in 0..20 {
+            creature.choose_direction(&world, &mut rng);
+            while creature.target_x.is_some() {
+                creature.move_towards_target(120, 60, 1.0);
+            }
+        }
+
+        let end_temperature = world.terrain[creature.y][creature.x].temperature;
+        assert!(
+            end_temperature < start_temperature - 5.0,
+            "moved from {} to {} degrees",
+            start_temperature,
+            end_temperature
+        );
+    }
+}
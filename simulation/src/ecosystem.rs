@@ -1,5 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+/// Fraction of capacity that regrows a fully grazed tile as if it were still planted.
+const SEED_BANK_FRACTION: f32 = 0.05;
+
+/// Scales `growth_rate` so half-grown tiles regrow at the same speed as before
+/// growth became logistic.
+const LOGISTIC_RATE_SCALE: f32 = 0.04;
+
 /// Per-tile ecosystem state tracking plant biomass and creature counts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TileEcosystem {
@@ -29,22 +36,34 @@ impl TileEcosystem {
         }
     }
 
+    /// Update capacity and growth rate after the tile's biome changes.
+    /// Existing biomass is kept but clamped to the new capacity.
+    pub fn set_biome_capacity(&mut self, max_biomass: f32, growth_rate: f32) {
+        self.max_biomass = max_biomass;
+        self.growth_rate = growth_rate;
+        self.plant_biomass = self.plant_biomass.min(max_biomass);
+    }
+
     /// Regrow plants towards carrying capacity (logistic growth).
     pub fn tick_plant_growth(&mut self) {
         if self.max_biomass <= 0.0 {
+            self.plant_biomass = 0.0;
             return;
         }
 
-        // Logistic growth: grows faster when biomass is low, slows near capacity
-        let growth_factor = 1.0 - (self.plant_biomass / self.max_biomass);
-        let growth = self.growth_rate * growth_factor * self.max_biomass * 0.02;
-        self.plant_biomass = (self.plant_biomass + growth).min(self.max_biomass);
+        // Logistic growth: r * B * (1 - B/K). A small seed bank stands in for
+        // B on bare tiles so overgrazed ground recovers, but slowly.
+        let biomass = self.plant_biomass.clamp(0.0, self.max_biomass);
+        let effective = biomass.max(self.max_biomass * SEED_BANK_FRACTION);
+        let growth =
+            self.growth_rate * LOGISTIC_RATE_SCALE * effective * (1.0 - biomass / self.max_biomass);
+        self.plant_biomass = (biomass + growth).clamp(0.0, self.max_biomass);
     }
 
     /// Consume some plant biomass. Returns the amount actually consumed.
     pub fn consume_plants(&mut self, amount: f32) -> f32 {
-        let consumed = amount.min(self.plant_biomass);
-        self.plant_biomass -= consumed;
+        let consumed = amount.max(0.0).min(self.plant_biomass.max(0.0));
+        self.plant_biomass = (self.plant_biomass - consumed).max(0.0);
         consumed
     }
 
@@ -61,3 +80,46 @@ pub fn tile_carrying_capacity(max_biomass: f32) -> u32 {
     // Roughly 1 creature per 5 units of max biomass
     (max_biomass / 5.0).max(0.0) as u32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn biomass_stays_within_capacity() {
+        let mut tile = TileEcosystem::new(60.0, 0.6);
+        for tick in 0..500 {
+            if tick % 7 == 0 {
+                tile.consume_plants(25.0);
+            }
+            tile.tick_plant_growth();
+            assert!(
+                (0.0..=tile.max_biomass).contains(&tile.plant_biomass),
+                "biomass {} outside [0, {}]",
+                tile.plant_biomass,
+                tile.max_biomass
+            );
+        }
+
+        tile.set_biome_capacity(5.0, 0.05);
+        assert!(tile.plant_biomass <= 5.0);
+        let available = tile.plant_biomass;
+        assert_eq!(tile.consume_plants(100.0), available);
+        assert_eq!(tile.plant_biomass, 0.0);
+    }
+
+    #[test]
+    fn grazed_tile_regrows_toward_capacity() {
+        let mut tile = TileEcosystem::new(60.0, 0.6);
+        tile.consume_plants(tile.max_biomass);
+        assert_eq!(tile.plant_biomass, 0.0);
+
+        let mut previous = tile.plant_biomass;
+        for _ in 0..1000 {
+            tile.tick_plant_growth();
+            assert!(tile.plant_biomass >= previous);
+            previous = tile.plant_biomass;
+        }
+        assert!(tile.plant_biomass > tile.max_biomass * 0.95);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{biome::BiomeType, simulation::SimConfig};
+use std::fmt;
+use wasm_bindgen::JsValue;
+
+/// Errors returned by simulation commands.
+///
+/// Each message starts with a stable code (see `SimError::code`) followed by
+/// `": "` and a human-readable detail, so JS callers can switch on the prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimError {
+    /// A world dimension was 0 or above the supported maximum.
+    InvalidDimensions { width: u32, height: u32 },
+    /// Tile coordinates outside the world.
+    InvalidTile(u32, u32),
+    /// A biome name that matches no `BiomeType`.
+    UnknownBiome(String),
+    /// Any other argument that failed validation.
+    InvalidArgument(String),
+}
+
+impl SimError {
+    /// Machine-readable error kind.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SimError::InvalidDimensions { .. } => "invalid_dimensions",
+            SimError::InvalidTile(..) => "invalid_tile",
+            SimError::UnknownBiome(_) => "unknown_biome",
+            SimError::InvalidArgument(_) => "invalid_argument",
+        }
+    }
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.code())?;
+        match self {
+            SimError::InvalidDimensions { width, height } => write!(
+                f,
+                "world dimensions {}x{} must be non-zero and at most {}x{}",
+                width,
+                height,
+                SimConfig::MAX_WORLD_WIDTH,
+                SimConfig::MAX_WORLD_HEIGHT
+            ),
+            SimError::InvalidTile(x, y) => write!(f, "tile ({}, {}) is out of bounds", x, y),
+            SimError::UnknownBiome(name) => {
+                let valid: Vec<&str> = BiomeType::ALL.iter().map(BiomeType::name).collect();
+                write!(
+                    f,
+                    "unknown biome '{}', expected one of: {}",
+                    name,
+                    valid.join(", ")
+                )
+            },
+            SimError::InvalidArgument(detail) => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl std::error::Error for SimError {}
+
+impl From<SimError> for JsValue {
+    fn from(err: SimError) -> JsValue {
+        JsValue::from_str(&err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_variant_renders_its_code_and_detail() {
+        assert_eq!(
+            SimError::InvalidDimensions {
+                width: 0,
+                height: 5000
+            }
+            .to_string(),
+            "invalid_dimensions: world dimensions 0x5000 must be non-zero and at most 2048x1024"
+        );
+        assert_eq!(
+            SimError::InvalidTile(3, 9).to_string(),
+            "invalid_tile: tile (3, 9) is out of bounds"
+        );
+        assert_eq!(
+            SimError::UnknownBiome("Swamp".to_string()).to_string(),
+            "unknown_biome: unknown biome 'Swamp', expected one of: Ice Cap, Ocean, Grassland, \
+             Forest, Taiga, Tundra, Desert, Rainforest"
+        );
+        assert_eq!(
+            SimError::InvalidArgument("altitude must be finite".to_string()).to_string(),
+            "invalid_argument: altitude must be finite"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use rand::{rngs::SmallRng, Rng};
+use rand_distr::{Distribution, Normal};
 use serde::{Deserialize, Serialize};
 
 /// Number of genes in the genome.
@@ -61,11 +62,29 @@ impl Genome {
     }
 
     /// Sexual reproduction: crossover of two parent genomes with mutation.
-    pub fn crossover(parent_a: &Genome, parent_b: &Genome, rng: &mut SmallRng) -> Self {
+    ///
+    /// Each gene mutates with probability `mutation_rate`, shifting by a normally
+    /// distributed amount with standard deviation `mutation_magnitude`. Both must lie
+    /// in [0, 1] as checked by `SimConfig::validate`; anything else disables mutation.
+    pub fn crossover(
+        parent_a: &Genome,
+        parent_b: &Genome,
+        mutation_rate: f32,
+        mutation_magnitude: f32,
+        rng: &mut SmallRng,
+    ) -> Self {
+        let mutation =
+            if (0.0..=1.0).contains(&mutation_rate) && (0.0..=1.0).contains(&mutation_magnitude) {
+                Normal::new(0.0, mutation_magnitude)
+                    .ok()
+                    .map(|step| (mutation_rate as f64, step))
+            } else {
+                None
+            };
         let mut genes = [0.0f32; GENE_COUNT];
         let crossover_point = rng.gen_range(1..GENE_COUNT);
 
-        for i in 0..GENE_COUNT {
+        for (i, gene) in genes.iter_mut().enumerate() {
             // Crossover
             let base = if i < crossover_point {
                 parent_a.genes[i]
@@ -81,15 +100,14 @@ impl Genome {
             };
 
             // Mutation
-            let mutated = if rng.gen_bool(0.08) {
-                // 8% per gene mutation rate
-                let delta = rng.gen_range(-0.15..0.15);
-                (blended + delta).clamp(0.0, 1.0)
-            } else {
-                blended
+            let mutated = match mutation {
+                Some((rate, step)) if rng.gen_bool(rate) => {
+                    (blended + step.sample(rng)).clamp(0.0, 1.0)
+                },
+                _ => blended,
             };
 
-            genes[i] = mutated;
+            *gene = mutated;
         }
 
         Self { genes }
@@ -164,6 +182,46 @@ impl Phenotype {
         }
     }
 
+    /// Names of the numeric traits accepted by `trait_value`.
+    pub const TRAIT_NAMES: &'static [&'static str] = &[
+        "body_size",
+        "speed",
+        "sense_range",
+        "diet",
+        "cold_tolerance",
+        "heat_tolerance",
+        "camouflage",
+        "aggression",
+        "max_age",
+        "fertility_threshold",
+        "offspring_count",
+        "metabolic_rate",
+        "drought_tolerance",
+        "terrain_mobility",
+    ];
+
+    /// Look up a trait by its field name.
+    pub fn trait_value(&self, name: &str) -> Option<f32> {
+        let value = match name {
+            "body_size" => self.body_size,
+            "speed" => self.speed,
+            "sense_range" => self.sense_range,
+            "diet" => self.diet,
+            "cold_tolerance" => self.cold_tolerance,
+            "heat_tolerance" => self.heat_tolerance,
+            "camouflage" => self.camouflage,
+            "aggression" => self.aggression,
+            "max_age" => self.max_age as f32,
+            "fertility_threshold" => self.fertility_threshold,
+            "offspring_count" => self.offspring_count as f32,
+            "metabolic_rate" => self.metabolic_rate,
+            "drought_tolerance" => self.drought_tolerance,
+            "terrain_mobility" => self.terrain_mobility,
+            _ => return None,
+        };
+        Some(value)
+    }
+
     /// Check if this creature can survive in the given temperature.
     pub fn can_tolerate_temperature(&self, temp: f32) -> bool {
         temp >= self.cold_tolerance && temp <= self.heat_tolerance
@@ -199,3 +257,74 @@ impl Phenotype {
         self.diet > 0.6
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn zero_mutation_rate_only_recombines_parent_genes() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let a = Genome::random(&mut rng);
+            let b = Genome::random(&mut rng);
+            let child = Genome::crossover(&a, &b, 0.0, 0.5, &mut rng);
+            for i in 0..GENE_COUNT {
+                let gene = child.genes[i];
+                let blended = (a.genes[i] + b.genes[i]) / 2.0;
+                assert!(gene == a.genes[i] || gene == b.genes[i] || gene == blended);
+            }
+        }
+    }
+
+    #[test]
+    fn high_mutation_rate_moves_offspring_away_from_parents() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let parent = Genome::random(&mut rng);
+        let mean_distance = |rate: f32, rng: &mut SmallRng| {
+            let total: f32 = (0..200)
+                .map(|_| Genome::crossover(&parent, &parent, rate, 0.3, rng).distance(&parent))
+                .sum();
+            total / 200.0
+        };
+        assert_eq!(mean_distance(0.0, &mut rng), 0.0);
+        assert!(mean_distance(1.0, &mut rng) > 0.1);
+    }
+
+    #[test]
+    fn mutation_magnitude_is_the_standard_deviation_of_the_step() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let parent = Genome {
+            genes: [0.5; GENE_COUNT],
+        };
+        let steps: Vec<f32> = (0..500)
+            .flat_map(|_| Genome::crossover(&parent, &parent, 1.0, 0.05, &mut rng).genes)
+            .map(|gene| gene - 0.5)
+            .collect();
+        let variance = steps.iter().map(|step| step * step).sum::<f32>() / steps.len() as f32;
+        assert!(
+            (variance.sqrt() - 0.05).abs() < 0.005,
+            "std dev {}",
+            variance.sqrt()
+        );
+    }
+
+    #[test]
+    fn invalid_mutation_settings_disable_mutation() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let parent = Genome::random(&mut rng);
+        for (rate, magnitude) in [
+            (f32::NAN, 0.3),
+            (1.0, f32::NAN),
+            (1.0, f32::INFINITY),
+            (f32::INFINITY, f32::NEG_INFINITY),
+            (1.0, f32::MAX),
+            (1.0, -0.3),
+            (1.5, 0.3),
+        ] {
+            let child = Genome::crossover(&parent, &parent, rate, magnitude, &mut rng);
+            assert_eq!(child.genes, parent.genes);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 pub mod biome;
 pub mod creature;
 pub mod ecosystem;
+pub mod error;
 pub mod genetics;
 pub mod math_util;
 pub mod perlin;
+pub mod region;
 pub mod simulation;
 pub mod species;
 pub mod wasm_api;
@@ -0,0 +1,86 @@
+use rand::{rngs::SmallRng, Rng};
+use serde::{Deserialize, Serialize};
+
+/// A connected body of water or landmass found by `World::label_regions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    pub id: u32,
+    pub kind: RegionKind,
+    pub name: String,
+    /// Number of tiles in the region.
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegionKind {
+    Ocean,
+    Sea,
+    Lake,
+    Continent,
+    Island,
+}
+
+/// Fraction of the map a water body must cover to count as an ocean.
+const OCEAN_MIN_FRACTION: f32 = 0.1;
+/// Fraction of the map a water body must cover to count as a sea.
+const SEA_MIN_FRACTION: f32 = 0.005;
+/// Fraction of the map a landmass must cover to count as a continent.
+const CONTINENT_MIN_FRACTION: f32 = 0.02;
+
+impl RegionKind {
+    /// Classify a region by whether it is land and what share of the map it covers.
+    pub fn classify(is_land: bool, size: usize, total_tiles: usize) -> Self {
+        let fraction = size as f32 / total_tiles.max(1) as f32;
+        if is_land {
+            if fraction >= CONTINENT_MIN_FRACTION {
+                RegionKind::Continent
+            } else {
+                RegionKind::Island
+            }
+        } else if fraction >= OCEAN_MIN_FRACTION {
+            RegionKind::Ocean
+        } else if fraction >= SEA_MIN_FRACTION {
+            RegionKind::Sea
+        } else {
+            RegionKind::Lake
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RegionKind::Ocean => "Ocean",
+            RegionKind::Sea => "Sea",
+            RegionKind::Lake => "Lake",
+            RegionKind::Continent => "Continent",
+            RegionKind::Island => "Island",
+        }
+    }
+
+    pub fn is_land(&self) -> bool {
+        matches!(self, RegionKind::Continent | RegionKind::Island)
+    }
+}
+
+/// Name parts for procedural region name generation.
+const ROOTS: &[&str] = &[
+    "Aster", "Bren", "Cald", "Dun", "Esk", "Fal", "Gorm", "Hal", "Ister", "Jarn", "Kel", "Lorn",
+    "Mar", "Nor", "Orm", "Pell", "Rhun", "Sund", "Tarn", "Vald",
+];
+
+const ENDINGS: &[&str] = &[
+    "a", "ia", "or", "en", "mark", "heim", "os", "wyn", "ar", "ith",
+];
+
+/// Generate a display name such as "Kelmark Sea" or "Isle of Tarnos".
+pub fn generate_region_name(kind: RegionKind, rng: &mut SmallRng) -> String {
+    let root = ROOTS[rng.gen_range(0..ROOTS.len())];
+    let ending = ENDINGS[rng.gen_range(0..ENDINGS.len())];
+    let base = format!("{}{}", root, ending);
+
+    match kind {
+        RegionKind::Ocean | RegionKind::Sea => format!("{} {}", base, kind.name()),
+        RegionKind::Lake => format!("Lake {}", base),
+        RegionKind::Continent => base,
+        RegionKind::Island => format!("Isle of {}", base),
+    }
+}
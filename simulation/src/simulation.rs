@@ -1,16 +1,21 @@
 use crate::{
+    biome::BiomeType,
     creature::{Creature, CreatureId},
-    ecosystem::TileEcosystem,
-    genetics::Genome,
-    species::SpeciesRegistry,
-    world::World,
+    ecosystem::{tile_carrying_capacity, TileEcosystem},
+    error::SimError,
+    genetics::{Genome, Phenotype},
+    species::{self, SpeciesRegistry},
+    world::{World, WorldGenConfig},
 };
 use rand::{rngs::SmallRng, Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Configuration for the simulation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimConfig {
     pub world_width: u32,
     pub world_height: u32,
@@ -20,6 +25,36 @@ pub struct SimConfig {
     pub max_creatures: usize,
     pub speciation_threshold: f32,
     pub speciation_check_interval: u64,
+    /// Run the per-creature movement phase in parallel with rayon. Each creature then
+    /// draws from its own RNG stream, so runs stay reproducible but differ from the
+    /// serial path. Off by default, since rayon runs on a single thread on wasm32.
+    /// Ignored unless the crate is built with the `parallel` cargo feature.
+    pub parallel: bool,
+    /// Multiplier on a hunter's chance to win a fight with prey it has found.
+    /// 1.0 leaves the odds unchanged.
+    pub hunt_success_base: f32,
+    /// Energy gained per unit of digested plant biomass.
+    pub plant_energy_density: f32,
+    /// Multiplier on the per-tick metabolic and movement costs of carnivores.
+    pub carnivore_metabolism_multiplier: f32,
+    /// Multiplier on the energy a creature needs before it can reproduce.
+    pub reproduction_energy_threshold: f32,
+    /// How many tiles away a creature looks for a mate. 0 only considers its own tile.
+    pub mate_search_radius: u32,
+    /// Per-gene probability that an offspring's gene mutates.
+    pub mutation_rate: f32,
+    /// Standard deviation of the normally distributed change a mutation applies to a gene.
+    pub mutation_magnitude: f32,
+    /// Color creatures by their own phenotype instead of their species color.
+    pub color_by_phenotype: bool,
+    /// Ticks between climate shifts. 0 disables climate drift.
+    pub climate_drift_interval: u64,
+    /// Peak temperature offset in degrees at the height of a warm or cold phase.
+    /// Warm phases are also drier, by 100 rainfall units per degree.
+    pub climate_drift_magnitude: f32,
+    /// Climate shifts per full warming and cooling cycle.
+    pub climate_cycle_length: u64,
+    pub world_gen: WorldGenConfig,
 }
 
 impl Default for SimConfig {
@@ -33,10 +68,129 @@ impl Default for SimConfig {
             max_creatures: 12000,
             speciation_threshold: 0.32,
             speciation_check_interval: 50,
+            parallel: false,
+            hunt_success_base: 1.0,
+            plant_energy_density: 1.0,
+            carnivore_metabolism_multiplier: 1.0,
+            reproduction_energy_threshold: 1.0,
+            mate_search_radius: 0,
+            mutation_rate: 0.08,
+            mutation_magnitude: 0.087,
+            color_by_phenotype: false,
+            climate_drift_interval: 0,
+            climate_drift_magnitude: 3.0,
+            climate_cycle_length: 20,
+            world_gen: WorldGenConfig::default(),
         }
     }
 }
 
+impl SimConfig {
+    /// Largest world width accepted by `Simulation::try_new`.
+    pub const MAX_WORLD_WIDTH: u32 = 2048;
+    /// Largest world height accepted by `Simulation::try_new`.
+    pub const MAX_WORLD_HEIGHT: u32 = 1024;
+    /// Largest `mate_search_radius` accepted by `Simulation::try_new`.
+    pub const MAX_MATE_SEARCH_RADIUS: u32 = 16;
+
+    /// A preset where herbivores and carnivores coexist over long runs.
+    ///
+    /// With the defaults, founding populations are too sparse for mates to meet, and
+    /// carnivores starve within a few hundred ticks. This preset searches for mates up
+    /// to 8 tiles away, starts 200 creatures per species, triples the energy in plants
+    /// and cuts carnivore upkeep to a fifth, so predators outlast the lean early ticks.
+    pub fn balanced() -> Self {
+        Self {
+            creatures_per_species: 200,
+            plant_energy_density: 3.0,
+            carnivore_metabolism_multiplier: 0.2,
+            mate_search_radius: 8,
+            ..Self::default()
+        }
+    }
+
+    /// Check that the world dimensions are non-zero and within the supported maximum,
+    /// that the ecology multipliers are finite and not negative, with a positive
+    /// reproduction threshold, that the mutation settings lie in [0, 1], and that the
+    /// world generation parameters are usable.
+    pub fn validate(&self) -> Result<(), SimError> {
+        if self.world_width == 0
+            || self.world_height == 0
+            || self.world_width > Self::MAX_WORLD_WIDTH
+            || self.world_height > Self::MAX_WORLD_HEIGHT
+        {
+            return Err(SimError::InvalidDimensions {
+                width: self.world_width,
+                height: self.world_height,
+            });
+        }
+        for (name, value) in [
+            ("hunt_success_base", self.hunt_success_base),
+            ("plant_energy_density", self.plant_energy_density),
+            (
+                "carnivore_metabolism_multiplier",
+                self.carnivore_metabolism_multiplier,
+            ),
+            (
+                "reproduction_energy_threshold",
+                self.reproduction_energy_threshold,
+            ),
+            ("climate_drift_magnitude", self.climate_drift_magnitude),
+        ] {
+            if !value.is_finite() {
+                return Err(SimError::InvalidArgument(format!(
+                    "{} must be finite",
+                    name
+                )));
+            }
+        }
+        for (name, value) in [
+            ("hunt_success_base", self.hunt_success_base),
+            ("plant_energy_density", self.plant_energy_density),
+            (
+                "carnivore_metabolism_multiplier",
+                self.carnivore_metabolism_multiplier,
+            ),
+        ] {
+            if value < 0.0 {
+                return Err(SimError::InvalidArgument(format!(
+                    "{} {} must not be negative",
+                    name, value
+                )));
+            }
+        }
+        // At 0 every creature with any energy would be ready to breed
+        if self.reproduction_energy_threshold <= 0.0 {
+            return Err(SimError::InvalidArgument(format!(
+                "reproduction_energy_threshold {} must be positive",
+                self.reproduction_energy_threshold
+            )));
+        }
+        Self::validate_mutation(self.mutation_rate, self.mutation_magnitude)?;
+        if self.mate_search_radius > Self::MAX_MATE_SEARCH_RADIUS {
+            return Err(SimError::InvalidArgument(format!(
+                "mate_search_radius {} must be at most {}",
+                self.mate_search_radius,
+                Self::MAX_MATE_SEARCH_RADIUS
+            )));
+        }
+        self.world_gen.validate()
+    }
+
+    /// Check that a mutation rate and magnitude both lie in [0, 1].
+    pub fn validate_mutation(rate: f32, magnitude: f32) -> Result<(), SimError> {
+        for (name, value) in [("mutation_rate", rate), ("mutation_magnitude", magnitude)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(SimError::InvalidArgument(format!(
+                    "{} {} must be within [0, 1]",
+                    name, value
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Population history entry for graphing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PopulationSnapshot {
@@ -61,20 +215,76 @@ pub struct Simulation {
     pub rng: SmallRng,
     pub seed: u32,
     pub population_history: Vec<PopulationSnapshot>,
-    /// Spatial index: tile (y, x) -> list of creature indices
+    /// Kills this food-web window: predator species -> prey species -> count.
+    /// Ordered maps keep serialized output identical between runs.
+    #[serde(default)]
+    pub kills_by_species: BTreeMap<u32, BTreeMap<u32, u32>>,
+    /// Spatial index: tile (y * width + x) -> list of creature indices
     #[serde(skip)]
-    spatial_index: HashMap<(usize, usize), Vec<usize>>,
+    spatial_index: Vec<Vec<usize>>,
 }
 
+/// Smallest density limit used for breeding, so sparse biomes still allow a pair to breed.
+const MIN_DENSITY_LIMIT: u32 = 4;
+
 fn default_rng() -> SmallRng {
     SmallRng::seed_from_u64(0)
 }
 
+/// Move a creature and apply environmental stress and metabolism for one tick.
+fn update_creature_environment(
+    creature: &mut Creature,
+    world: &World,
+    config: &SimConfig,
+    tick: u64,
+    rng: &mut SmallRng,
+) {
+    if !creature.alive {
+        return;
+    }
+
+    // Choose new direction periodically
+    if creature.target_x.is_none() || tick.is_multiple_of(3) {
+        creature.choose_direction(world, rng);
+    }
+
+    // Move
+    creature.move_towards_target(
+        world.width as usize,
+        world.height as usize,
+        config.carnivore_metabolism_multiplier,
+    );
+
+    // Apply environmental stress
+    let cell = &world.terrain[creature.y][creature.x];
+    creature.apply_temperature_stress(cell.temperature);
+    creature.apply_drought_stress(cell.rainfall);
+
+    // Metabolism
+    creature.tick_metabolism(config.carnivore_metabolism_multiplier);
+}
+
 impl Simulation {
+    /// Ticks covered by `kills_by_species` before it is reset.
+    pub const FOOD_WEB_WINDOW: u64 = 100;
+
+    /// Create a new simulation, rejecting configs that fail `SimConfig::validate`.
+    pub fn try_new(seed: u32, config: SimConfig) -> Result<Self, SimError> {
+        config.validate()?;
+        Ok(Self::new(seed, config))
+    }
+
     /// Create a new simulation with the given seed and config.
+    ///
+    /// World generation panics on zero dimensions; use `try_new` for untrusted input.
     pub fn new(seed: u32, config: SimConfig) -> Self {
         let rng = SmallRng::seed_from_u64(seed as u64 + 1000);
-        let world = World::generate(config.world_width, config.world_height, seed);
+        let world = World::generate_with_config(
+            config.world_width,
+            config.world_height,
+            seed,
+            &config.world_gen,
+        );
 
         // Initialize ecosystems
         let mut ecosystems = Vec::with_capacity(config.world_height as usize);
@@ -99,7 +309,8 @@ impl Simulation {
             rng,
             seed,
             population_history: Vec::new(),
-            spatial_index: HashMap::new(),
+            kills_by_species: BTreeMap::new(),
+            spatial_index: Vec::new(),
         };
 
         sim.populate_initial_creatures();
@@ -107,6 +318,12 @@ impl Simulation {
         sim
     }
 
+    /// Branch an independent copy of this simulation, including its RNG state.
+    /// Advancing the fork and the original with the same inputs gives the same results.
+    pub fn fork(&self) -> Simulation {
+        self.clone()
+    }
+
     fn populate_initial_creatures(&mut self) {
         let habitable = self.world.habitable_tiles();
         if habitable.is_empty() {
@@ -152,14 +369,23 @@ impl Simulation {
         }
     }
 
+    fn tile_index(&self, x: usize, y: usize) -> usize {
+        y * self.config.world_width as usize + x
+    }
+
     fn rebuild_spatial_index(&mut self) {
-        self.spatial_index.clear();
+        let tile_count = (self.config.world_width * self.config.world_height) as usize;
+        if self.spatial_index.len() != tile_count {
+            self.spatial_index = vec![Vec::new(); tile_count];
+        }
+        // Clear buckets in place so their allocations are reused across ticks
+        for bucket in self.spatial_index.iter_mut() {
+            bucket.clear();
+        }
         for (idx, creature) in self.creatures.iter().enumerate() {
             if creature.alive {
-                self.spatial_index
-                    .entry((creature.y, creature.x))
-                    .or_insert_with(Vec::new)
-                    .push(idx);
+                let tile = self.tile_index(creature.x, creature.y);
+                self.spatial_index[tile].push(idx);
             }
         }
     }
@@ -168,6 +394,10 @@ impl Simulation {
     pub fn tick(&mut self) {
         self.tick += 1;
 
+        if self.tick.is_multiple_of(Self::FOOD_WEB_WINDOW) {
+            self.kills_by_species.clear();
+        }
+
         // Reset ecosystem counts
         for row in &mut self.ecosystems {
             for eco in row.iter_mut() {
@@ -176,34 +406,33 @@ impl Simulation {
             }
         }
 
-        let width = self.config.world_width as usize;
-        let height = self.config.world_height as usize;
-
         // Phase 1: Movement and environment
-        for i in 0..self.creatures.len() {
-            if !self.creatures[i].alive {
-                continue;
+        if self.config.parallel && cfg!(feature = "parallel") {
+            // Each creature gets its own RNG derived from a per-tick base seed, so the result
+            // does not depend on how rayon schedules the work.
+            #[cfg(feature = "parallel")]
+            {
+                let base_seed: u64 = self.rng.gen();
+                let world = &self.world;
+                let config = &self.config;
+                let tick = self.tick;
+                self.creatures.par_iter_mut().for_each(|creature| {
+                    let mut rng = SmallRng::seed_from_u64(
+                        base_seed ^ creature.id.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                    );
+                    update_creature_environment(creature, world, config, tick, &mut rng);
+                });
             }
-
-            // Choose new direction periodically
-            if self.creatures[i].target_x.is_none() || self.tick % 3 == 0 {
-                self.creatures[i].choose_direction(width, height, &mut self.rng);
+        } else {
+            for creature in self.creatures.iter_mut() {
+                update_creature_environment(
+                    creature,
+                    &self.world,
+                    &self.config,
+                    self.tick,
+                    &mut self.rng,
+                );
             }
-
-            // Move
-            self.creatures[i].move_towards_target(width, height);
-
-            // Apply environmental stress
-            let x = self.creatures[i].x;
-            let y = self.creatures[i].y;
-            let temp = self.world.terrain[y][x].temperature;
-            let rain = self.world.terrain[y][x].rainfall;
-
-            self.creatures[i].apply_temperature_stress(temp);
-            self.creatures[i].apply_drought_stress(rain);
-
-            // Metabolism
-            self.creatures[i].tick_metabolism();
         }
 
         // Phase 2: Feeding
@@ -234,22 +463,24 @@ impl Simulation {
                 let x = self.creatures[i].x;
                 let y = self.creatures[i].y;
                 let available = self.ecosystems[y][x].plant_biomass;
-                let consumed = self.creatures[i].eat_plants(available);
+                let consumed =
+                    self.creatures[i].eat_plants(available, self.config.plant_energy_density);
                 self.ecosystems[y][x].consume_plants(consumed);
             }
         }
 
         // Carnivore hunting
-        let spatial_clone = self.spatial_index.clone();
+        // The index is taken out for the phase so creatures can be borrowed mutably.
+        let spatial_index = std::mem::take(&mut self.spatial_index);
         for i in 0..self.creatures.len() {
             if !self.creatures[i].alive || self.creatures[i].phenotype.diet < 0.2 {
                 continue;
             }
 
-            let pos = (self.creatures[i].y, self.creatures[i].x);
-            if let Some(tile_creatures) = spatial_clone.get(&pos) {
+            let tile = self.tile_index(self.creatures[i].x, self.creatures[i].y);
+            {
                 // Find a prey target on the same tile
-                for &prey_idx in tile_creatures {
+                for &prey_idx in &spatial_index[tile] {
                     if prey_idx == i || !self.creatures[prey_idx].alive {
                         continue;
                     }
@@ -267,7 +498,13 @@ impl Simulation {
                             (&mut right[0], &mut left[prey_idx])
                         };
 
-                        if hunter.hunt(prey, &mut self.rng) {
+                        if hunter.hunt(prey, self.config.hunt_success_base, &mut self.rng) {
+                            *self
+                                .kills_by_species
+                                .entry(hunter.species_id)
+                                .or_default()
+                                .entry(prey.species_id)
+                                .or_default() += 1;
                             break; // One kill per tick
                         }
                     }
@@ -278,53 +515,84 @@ impl Simulation {
         // Phase 3: Reproduction
         let mut new_creatures: Vec<Creature> = Vec::new();
 
-        let spatial_clone = self.spatial_index.clone();
         for i in 0..self.creatures.len() {
-            if !self.creatures[i].can_reproduce() {
+            let threshold = self.config.reproduction_energy_threshold;
+            if !self.creatures[i].can_reproduce(threshold) {
                 continue;
             }
             if self.creatures.len() + new_creatures.len() >= self.config.max_creatures {
                 break;
             }
 
-            let pos = (self.creatures[i].y, self.creatures[i].x);
-            if let Some(tile_creatures) = spatial_clone.get(&pos) {
-                for &partner_idx in tile_creatures {
-                    if partner_idx == i
-                        || !self.creatures[partner_idx].alive
-                        || !self.creatures[partner_idx].can_reproduce()
-                    {
-                        continue;
-                    }
+            // Choose the most appealing eligible partner within the search radius
+            let (x, y) = (self.creatures[i].x, self.creatures[i].y);
+            let radius = self.config.mate_search_radius as i32;
+            let mut best: Option<(usize, f32)> = None;
+            for dy in -radius..=radius {
+                let ny = y as i32 + dy;
+                if ny < 0 || ny >= self.config.world_height as i32 {
+                    continue;
+                }
+                for dx in -radius..=radius {
+                    let nx = (x as i32 + dx).rem_euclid(self.config.world_width as i32);
+                    let tile = self.tile_index(nx as usize, ny as usize);
+                    for &partner_idx in &spatial_index[tile] {
+                        let partner = &self.creatures[partner_idx];
+                        if partner_idx == i || !partner.alive || !partner.can_reproduce(threshold) {
+                            continue;
+                        }
 
-                    // Must be same species (or close enough genetically)
-                    if self.creatures[i].species_id != self.creatures[partner_idx].species_id {
-                        continue;
-                    }
+                        // Must be same species
+                        if partner.species_id != self.creatures[i].species_id {
+                            continue;
+                        }
 
-                    let next_id = self.next_creature_id;
+                        let score = partner.mate_score(&self.creatures[i]);
+                        if best.is_none_or(|(_, best_score)| score > best_score) {
+                            best = Some((partner_idx, score));
+                        }
+                    }
+                }
+            }
+            let Some((partner_idx, _)) = best else {
+                continue;
+            };
 
-                    let (parent_a, parent_b) = if i < partner_idx {
-                        let (left, right) = self.creatures.split_at_mut(partner_idx);
-                        (&mut left[i], &mut right[0])
-                    } else {
-                        let (left, right) = self.creatures.split_at_mut(i);
-                        (&mut right[0], &mut left[partner_idx])
-                    };
+            // Crowded tiles leave less for offspring, so breeding succeeds less often
+            let success = self.breeding_success(x, y);
+            if success < 1.0 && !self.rng.gen_bool(success as f64) {
+                continue;
+            }
 
-                    let offspring = parent_a.reproduce(parent_b, next_id, &mut self.rng);
-                    self.next_creature_id += offspring.len() as u64;
+            let next_id = self.next_creature_id;
 
-                    for child in offspring {
-                        self.species_registry
-                            .record_birth(child.species_id, child.generation);
-                        new_creatures.push(child);
-                    }
-                    break; // One reproduction event per tick per creature
-                }
+            let (parent_a, parent_b) = if i < partner_idx {
+                let (left, right) = self.creatures.split_at_mut(partner_idx);
+                (&mut left[i], &mut right[0])
+            } else {
+                let (left, right) = self.creatures.split_at_mut(i);
+                (&mut right[0], &mut left[partner_idx])
+            };
+
+            let offspring = parent_a.reproduce(
+                parent_b,
+                next_id,
+                threshold,
+                self.config.mutation_rate,
+                self.config.mutation_magnitude,
+                &mut self.rng,
+            );
+            self.next_creature_id += offspring.len() as u64;
+
+            for child in offspring {
+                self.species_registry
+                    .record_birth(child.species_id, child.generation);
+                new_creatures.push(child);
             }
         }
 
+        self.spatial_index = spatial_index;
+
         // Add new creatures
         self.creatures.extend(new_creatures);
 
@@ -341,12 +609,26 @@ impl Simulation {
         });
 
         // Phase 5: Speciation check (periodic)
-        if self.tick % self.config.speciation_check_interval == 0 {
+        if self
+            .tick
+            .is_multiple_of(self.config.speciation_check_interval)
+        {
             self.check_speciation();
         }
 
-        // Phase 6: Record population snapshot
-        if self.tick % 10 == 0 {
+        // Phase 6: Climate drift (periodic, optional)
+        if self.config.climate_drift_interval > 0
+            && self.tick.is_multiple_of(self.config.climate_drift_interval)
+        {
+            // Follow a sine wave so warm and cold phases alternate
+            let step = self.tick / self.config.climate_drift_interval;
+            let delta_temp = self.climate_cycle_offset(step) - self.climate_cycle_offset(step - 1);
+            let delta_rain = -delta_temp * 100.0;
+            self.apply_climate_shift(delta_temp, delta_rain);
+        }
+
+        // Phase 7: Record population snapshot
+        if self.tick.is_multiple_of(10) {
             let herbivores = self
                 .creatures
                 .iter()
@@ -374,6 +656,94 @@ impl Simulation {
         }
     }
 
+    /// Temperature offset of the climate cycle after `step` shifts.
+    fn climate_cycle_offset(&self, step: u64) -> f32 {
+        let length = self.config.climate_cycle_length.max(1);
+        let phase = (step % length) as f32 / length as f32;
+        self.config.climate_drift_magnitude * (phase * std::f32::consts::TAU).sin()
+    }
+
+    /// Shift the world's climate and update tile ecosystems to their new biomes.
+    /// Returns the number of tiles whose dominant biome changed.
+    pub fn apply_climate_shift(&mut self, delta_temp: f32, delta_rain: f32) -> usize {
+        let changed = self.world.apply_climate_shift(delta_temp, delta_rain);
+        if changed > 0 {
+            for y in 0..self.world.height as usize {
+                for x in 0..self.world.width as usize {
+                    self.refresh_tile_ecosystem(x, y);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Paint a tile with a biome by name, resetting its plant capacity to match.
+    /// Painting across the waterline also lifts or sinks the tile and relabels regions.
+    pub fn set_tile_biome(&mut self, x: u32, y: u32, biome: &str) -> Result<(), SimError> {
+        let biome =
+            BiomeType::from_name(biome).ok_or_else(|| SimError::UnknownBiome(biome.to_string()))?;
+        let was_land = match self
+            .world
+            .terrain
+            .get(y as usize)
+            .and_then(|r| r.get(x as usize))
+        {
+            Some(cell) => cell.is_land(),
+            None => return Err(SimError::InvalidTile(x, y)),
+        };
+        self.world.set_tile_biome(x as usize, y as usize, biome);
+        self.refresh_tile_ecosystem(x as usize, y as usize);
+        if self.world.terrain[y as usize][x as usize].is_land() != was_land {
+            self.world.label_regions();
+        }
+        Ok(())
+    }
+
+    /// Set a tile's altitude, reclassifying its biome and plant capacity.
+    pub fn set_tile_altitude(&mut self, x: u32, y: u32, altitude: f32) -> Result<(), SimError> {
+        if !altitude.is_finite() {
+            return Err(SimError::InvalidArgument(format!(
+                "altitude must be a finite number, got {}",
+                altitude
+            )));
+        }
+        let was_land = match self
+            .world
+            .terrain
+            .get(y as usize)
+            .and_then(|r| r.get(x as usize))
+        {
+            Some(cell) => cell.is_land(),
+            None => return Err(SimError::InvalidTile(x, y)),
+        };
+        self.world
+            .set_tile_altitude(x as usize, y as usize, altitude);
+        self.refresh_tile_ecosystem(x as usize, y as usize);
+        // Crossing the waterline can split or join regions
+        if self.world.terrain[y as usize][x as usize].is_land() != was_land {
+            self.world.label_regions();
+        }
+        Ok(())
+    }
+
+    /// Chance that a mating on this tile produces offspring. It is 1 up to half the
+    /// tile's carrying capacity and falls to 0 as the creature count reaches it.
+    fn breeding_success(&self, x: usize, y: usize) -> f32 {
+        let eco = &self.ecosystems[y][x];
+        let limit = tile_carrying_capacity(eco.max_biomass).max(MIN_DENSITY_LIMIT) as f32;
+        ((limit - eco.creature_count as f32) / (limit / 2.0)).clamp(0.0, 1.0)
+    }
+
+    fn refresh_tile_ecosystem(&mut self, x: usize, y: usize) {
+        let biome = self.world.terrain[y][x].dominant_biome();
+        self.ecosystems[y][x]
+            .set_biome_capacity(biome.max_plant_biomass(), biome.plant_growth_rate());
+    }
+
+    /// Split creatures that have drifted from their species into daughter species.
+    ///
+    /// Moved creatures leave their old species through `record_death`, so an old
+    /// species whose members all move is logged as an Extinction.
     fn check_speciation(&mut self) {
         let mut reassignments: Vec<(usize, u32)> = Vec::new();
 
@@ -457,6 +827,57 @@ impl Simulation {
         buf
     }
 
+    /// Get a terrain RGBA buffer resized to `out_w` x `out_h` for minimaps.
+    /// Each pixel takes the most common biome in its box of tiles; when the
+    /// output is larger than the world, the nearest tile is used. The output is
+    /// capped at `MAX_WORLD_WIDTH` x `MAX_WORLD_HEIGHT`.
+    pub fn get_terrain_thumbnail_rgba(&self, out_w: u32, out_h: u32) -> Vec<u8> {
+        let w = self.config.world_width as usize;
+        let h = self.config.world_height as usize;
+        let out_w = out_w.min(SimConfig::MAX_WORLD_WIDTH) as usize;
+        let out_h = out_h.min(SimConfig::MAX_WORLD_HEIGHT) as usize;
+        let mut buf = vec![0u8; out_w * out_h * 4];
+        if w == 0 || h == 0 {
+            return buf;
+        }
+
+        let mut counts = [0u32; BiomeType::ALL.len()];
+        for oy in 0..out_h {
+            let y0 = oy * h / out_h;
+            let y1 = ((oy + 1) * h / out_h).max(y0 + 1);
+            for ox in 0..out_w {
+                let x0 = ox * w / out_w;
+                let x1 = ((ox + 1) * w / out_w).max(x0 + 1);
+
+                counts.fill(0);
+                for row in &self.world.terrain[y0..y1] {
+                    for cell in &row[x0..x1] {
+                        let biome = cell.dominant_biome();
+                        if let Some(i) = BiomeType::ALL.iter().position(|&b| b == biome) {
+                            counts[i] += 1;
+                        }
+                    }
+                }
+                // Ties go to the biome listed first in BiomeType::ALL
+                let majority = counts
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(i, &count)| (count, std::cmp::Reverse(i)))
+                    .map(|(i, _)| BiomeType::ALL[i])
+                    .unwrap_or(BiomeType::Ocean);
+
+                let [r, g, b] = majority.color();
+                let idx = (oy * out_w + ox) * 4;
+                buf[idx] = r;
+                buf[idx + 1] = g;
+                buf[idx + 2] = b;
+                buf[idx + 3] = 255;
+            }
+        }
+
+        buf
+    }
+
     /// Get creature data as flat buffer: [x, y, r, g, b, size, diet, ...] per creature.
     /// Each creature takes 8 floats.
     pub fn get_creature_data(&self) -> Vec<f32> {
@@ -467,12 +888,15 @@ impl Simulation {
                 continue;
             }
 
-            let color = self
-                .species_registry
-                .species
-                .get(&c.species_id)
-                .map(|s| s.color)
-                .unwrap_or([200, 200, 200]);
+            let color = if self.config.color_by_phenotype {
+                species::phenotype_color(&c.phenotype)
+            } else {
+                self.species_registry
+                    .species
+                    .get(&c.species_id)
+                    .map(|s| s.color)
+                    .unwrap_or([200, 200, 200])
+            };
 
             buf.push(c.x as f32 + c.sub_x);
             buf.push(c.y as f32 + c.sub_y);
@@ -524,6 +948,215 @@ impl Simulation {
         serde_json::to_string(&self.population_history).unwrap_or_default()
     }
 
+    /// Bin living creatures by a phenotype trait into `buckets` equal-width bins.
+    pub fn get_phenotype_histogram_json(&self, trait_name: &str, buckets: u32) -> String {
+        if !Phenotype::TRAIT_NAMES.contains(&trait_name) {
+            return serde_json::json!({
+                "error": format!("unknown trait '{}'", trait_name),
+                "valid_traits": Phenotype::TRAIT_NAMES,
+            })
+            .to_string();
+        }
+        if buckets == 0 {
+            return serde_json::json!({ "error": "bucket count must be at least 1" }).to_string();
+        }
+
+        let values: Vec<f32> = self
+            .creatures
+            .iter()
+            .filter(|c| c.alive)
+            .filter_map(|c| c.phenotype.trait_value(trait_name))
+            .collect();
+
+        let mut counts = vec![0u32; buckets as usize];
+        if values.is_empty() {
+            return serde_json::json!({
+                "trait": trait_name,
+                "count": 0,
+                "min": null,
+                "max": null,
+                "mean": null,
+                "buckets": counts,
+            })
+            .to_string();
+        }
+
+        let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let span = max - min;
+
+        for value in &values {
+            let bucket = if span > 0.0 {
+                (((value - min) / span) * buckets as f32) as usize
+            } else {
+                0
+            };
+            counts[bucket.min(buckets as usize - 1)] += 1;
+        }
+
+        serde_json::json!({
+            "trait": trait_name,
+            "count": values.len(),
+            "min": min,
+            "max": max,
+            "mean": mean,
+            "buckets": counts,
+        })
+        .to_string()
+    }
+
+    /// Get the food web: per living species, its average diet and body size
+    /// and the species it has preyed on during the current window.
+    pub fn get_food_web_json(&self) -> String {
+        let mut totals: HashMap<u32, (u32, f32, f32)> = HashMap::new();
+        for c in self.creatures.iter().filter(|c| c.alive) {
+            let entry = totals.entry(c.species_id).or_default();
+            entry.0 += 1;
+            entry.1 += c.phenotype.diet;
+            entry.2 += c.phenotype.body_size;
+        }
+
+        let living = self.species_registry.living_species();
+
+        let species_info: Vec<serde_json::Value> = living
+            .iter()
+            .map(|s| {
+                let (count, diet_sum, size_sum) = totals.get(&s.id).copied().unwrap_or_default();
+                let count = count.max(1) as f32;
+
+                let mut prey: Vec<(u32, u32)> = self
+                    .kills_by_species
+                    .get(&s.id)
+                    .map(|kills| kills.iter().map(|(id, n)| (*id, *n)).collect())
+                    .unwrap_or_default();
+                prey.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+                let prey: Vec<serde_json::Value> = prey
+                    .into_iter()
+                    .map(|(prey_id, kills)| {
+                        let name = self
+                            .species_registry
+                            .species
+                            .get(&prey_id)
+                            .map(|p| p.name.as_str())
+                            .unwrap_or("Unknown");
+                        serde_json::json!({
+                            "species_id": prey_id,
+                            "name": name,
+                            "kills": kills,
+                        })
+                    })
+                    .collect();
+
+                serde_json::json!({
+                    "id": s.id,
+                    "name": s.name,
+                    "diet_label": s.diet_label.name(),
+                    "avg_diet": diet_sum / count,
+                    "avg_body_size": size_sum / count,
+                    "prey": prey,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "tick": self.tick,
+            "window": Self::FOOD_WEB_WINDOW,
+            "species": species_info,
+        })
+        .to_string()
+    }
+
+    /// Get every species ever recorded with its ancestor and lifespan, plus the
+    /// origination/extinction event log, as JSON.
+    pub fn get_phylogeny_json(&self) -> String {
+        let nodes: Vec<serde_json::Value> = self
+            .species_registry
+            .species
+            .values()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.id,
+                    "name": s.name,
+                    "color": s.color,
+                    "ancestor_id": s.ancestor_id,
+                    "appeared_tick": s.appeared_tick,
+                    "extinct_tick": s.extinct_tick,
+                    "peak_population": s.peak_population,
+                    "diet_label": s.diet_label.name(),
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "tick": self.tick,
+            "species": nodes,
+            "events": self.species_registry.events,
+        })
+        .to_string()
+    }
+
+    /// Get a rectangle of tiles as JSON, row by row, so a UI can load the map in chunks.
+    /// `x0` wraps around the world horizontally and `w` is capped at the world width;
+    /// `y0` and `h` are clamped to the world's rows. The response reports the
+    /// rectangle actually returned.
+    pub fn get_world_region_json(&self, x0: u32, y0: u32, w: u32, h: u32) -> String {
+        let width = self.config.world_width;
+        let height = self.config.world_height;
+        let x0 = if width == 0 { 0 } else { x0 % width };
+        let y0 = y0.min(height);
+        let w = w.min(width);
+        let h = h.min(height - y0);
+
+        let mut tiles = Vec::with_capacity((w * h) as usize);
+        for y in y0..y0 + h {
+            for dx in 0..w {
+                let x = (x0 + dx) % width;
+                let cell = &self.world.terrain[y as usize][x as usize];
+                let eco = &self.ecosystems[y as usize][x as usize];
+                tiles.push(serde_json::json!({
+                    "x": x,
+                    "y": y,
+                    "altitude": cell.altitude,
+                    "temperature": cell.temperature,
+                    "rainfall": cell.rainfall,
+                    "biome": cell.dominant_biome().name(),
+                    "region_id": cell.region_id,
+                    "plant_biomass": eco.plant_biomass,
+                }));
+            }
+        }
+
+        serde_json::json!({
+            "x0": x0,
+            "y0": y0,
+            "width": w,
+            "height": h,
+            "tiles": tiles,
+        })
+        .to_string()
+    }
+
+    /// Get the world's named water bodies and landmasses as JSON.
+    pub fn get_regions_json(&self) -> String {
+        let regions: Vec<serde_json::Value> = self
+            .world
+            .regions
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "id": r.id,
+                    "kind": r.kind.name(),
+                    "name": r.name,
+                    "size": r.size,
+                })
+            })
+            .collect();
+
+        serde_json::json!({ "regions": regions }).to_string()
+    }
+
     /// Get info about a specific tile.
     pub fn get_tile_info_json(&self, x: u32, y: u32) -> String {
         let x = x as usize;
@@ -575,7 +1208,7 @@ impl Simulation {
         let biome_presences: Vec<serde_json::Value> = cell
             .biome_presences
             .iter()
-            .map(|(bt, p): &(crate::biome::BiomeType, f32)| {
+            .map(|(bt, p): &(BiomeType, f32)| {
                 serde_json::json!({
                     "biome": bt.name(),
                     "presence": format!("{:.0}%", p * 100.0),
@@ -591,6 +1224,7 @@ impl Simulation {
             "rainfall": format!("{:.0}", cell.rainfall),
             "biome": biome.name(),
             "biome_presences": biome_presences,
+            "region": self.world.region_at(x, y).map(|r| r.name.as_str()),
             "plant_biomass": format!("{:.1}", eco.plant_biomass),
             "max_biomass": format!("{:.1}", eco.max_biomass),
             "creature_count": eco.creature_count,
@@ -599,3 +1233,455 @@ impl Simulation {
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_config() -> SimConfig {
+        SimConfig {
+            world_width: 80,
+            world_height: 40,
+            creatures_per_species: 20,
+            ..SimConfig::default()
+        }
+    }
+
+    fn run(seed: u32, config: SimConfig, ticks: u32) -> Simulation {
+        let mut sim = Simulation::new(seed, config);
+        for _ in 0..ticks {
+            sim.tick();
+        }
+        sim
+    }
+
+    #[test]
+    fn serial_and_parallel_runs_are_reproducible() {
+        for parallel in [false, true] {
+            let config = SimConfig {
+                parallel,
+                ..small_config()
+            };
+            let a = run(7, config.clone(), 60);
+            let b = run(7, config, 60);
+            assert!(!a.creatures.is_empty());
+            assert_eq!(
+                serde_json::to_string(&a.creatures).unwrap(),
+                serde_json::to_string(&b.creatures).unwrap(),
+                "parallel = {}",
+                parallel
+            );
+        }
+    }
+
+    #[test]
+    fn phenotype_histogram_counts_every_living_creature() {
+        let sim = run(3, small_config(), 20);
+        let living: Vec<&Creature> = sim.creatures.iter().filter(|c| c.alive).collect();
+        assert!(!living.is_empty());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&sim.get_phenotype_histogram_json("body_size", 8)).unwrap();
+        let buckets: Vec<u64> = json["buckets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b.as_u64().unwrap())
+            .collect();
+        assert_eq!(json["trait"], "body_size");
+        assert_eq!(buckets.len(), 8);
+        assert_eq!(buckets.iter().sum::<u64>(), living.len() as u64);
+        assert_eq!(json["count"], living.len());
+
+        let min = living
+            .iter()
+            .map(|c| c.phenotype.body_size)
+            .fold(f32::INFINITY, f32::min);
+        let max = living
+            .iter()
+            .map(|c| c.phenotype.body_size)
+            .fold(f32::NEG_INFINITY, f32::max);
+        assert_eq!(json["min"].as_f64().unwrap() as f32, min);
+        assert_eq!(json["max"].as_f64().unwrap() as f32, max);
+        let mean = json["mean"].as_f64().unwrap() as f32;
+        assert!(min <= mean && mean <= max);
+    }
+
+    #[test]
+    fn phenotype_histogram_rejects_bad_arguments() {
+        let sim = Simulation::new(3, small_config());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&sim.get_phenotype_histogram_json("wingspan", 8)).unwrap();
+        assert_eq!(json["error"], "unknown trait 'wingspan'");
+        assert_eq!(
+            json["valid_traits"].as_array().unwrap().len(),
+            Phenotype::TRAIT_NAMES.len()
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&sim.get_phenotype_histogram_json("body_size", 0)).unwrap();
+        assert!(json["error"].is_string());
+    }
+
+    #[test]
+    fn validate_rejects_non_finite_multipliers() {
+        assert!(SimConfig::balanced().validate().is_ok());
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, -1.0] {
+            let configs = [
+                SimConfig {
+                    hunt_success_base: value,
+                    ..SimConfig::default()
+                },
+                SimConfig {
+                    plant_energy_density: value,
+                    ..SimConfig::default()
+                },
+                SimConfig {
+                    carnivore_metabolism_multiplier: value,
+                    ..SimConfig::default()
+                },
+                SimConfig {
+                    reproduction_energy_threshold: value,
+                    ..SimConfig::default()
+                },
+                SimConfig {
+                    mutation_rate: value,
+                    ..SimConfig::default()
+                },
+                SimConfig {
+                    mutation_magnitude: value,
+                    ..SimConfig::default()
+                },
+            ];
+            for config in configs {
+                assert!(matches!(
+                    config.validate(),
+                    Err(SimError::InvalidArgument(_))
+                ));
+            }
+        }
+
+        let config = SimConfig {
+            reproduction_energy_threshold: 0.0,
+            ..SimConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(SimError::InvalidArgument(_))
+        ));
+        let config = SimConfig {
+            hunt_success_base: 0.0,
+            plant_energy_density: 0.0,
+            carnivore_metabolism_multiplier: 0.0,
+            ..SimConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = SimConfig {
+            mate_search_radius: SimConfig::MAX_MATE_SEARCH_RADIUS + 1,
+            ..SimConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(SimError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_mutation_settings_outside_unit_interval() {
+        for (rate, magnitude) in [(0.0, 0.0), (1.0, 1.0), (0.08, 0.087)] {
+            assert!(SimConfig::validate_mutation(rate, magnitude).is_ok());
+        }
+        for value in [-0.1, 1.5, f32::MAX, f32::NAN] {
+            for config in [
+                SimConfig {
+                    mutation_rate: value,
+                    ..SimConfig::default()
+                },
+                SimConfig {
+                    mutation_magnitude: value,
+                    ..SimConfig::default()
+                },
+            ] {
+                assert!(matches!(
+                    config.validate(),
+                    Err(SimError::InvalidArgument(_))
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn climate_drift_warms_then_cools() {
+        let config = SimConfig {
+            climate_drift_interval: 1,
+            climate_drift_magnitude: 3.0,
+            climate_cycle_length: 4,
+            ..small_config()
+        };
+        let mut sim = Simulation::new(1, config);
+        let (x, y) = (40, 20);
+        let start = sim.world.terrain[y][x].temperature;
+        for expected in [3.0, 0.0, -3.0, 0.0] {
+            sim.tick();
+            let offset = sim.world.terrain[y][x].temperature - start;
+            assert!(
+                (offset - expected).abs() < 1e-3,
+                "offset {} != {}",
+                offset,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn painting_grassland_updates_tile_capacity() {
+        let mut sim = Simulation::new(1, small_config());
+        let (x, y) = (0..40)
+            .flat_map(|y| (0..80).map(move |x| (x, y)))
+            .find(|&(x, y)| sim.world.terrain[y][x].dominant_biome() == BiomeType::Ocean)
+            .unwrap();
+
+        sim.set_tile_biome(x as u32, y as u32, "Grassland").unwrap();
+        let ecosystem = &sim.ecosystems[y][x];
+        assert_eq!(
+            ecosystem.max_biomass,
+            BiomeType::Grassland.max_plant_biomass()
+        );
+        assert_eq!(
+            ecosystem.growth_rate,
+            BiomeType::Grassland.plant_growth_rate()
+        );
+        assert_ne!(ecosystem.max_biomass, BiomeType::Ocean.max_plant_biomass());
+
+        // The painted tile is now land, so it no longer belongs to an ocean region
+        assert!(sim.world.terrain[y][x].is_land());
+        let region = sim.world.region_at(x, y).unwrap();
+        assert!(
+            region.kind.is_land(),
+            "painted tile is in {:?}",
+            region.kind
+        );
+
+        sim.set_tile_biome(x as u32, y as u32, "Ocean").unwrap();
+        assert!(!sim.world.terrain[y][x].is_land());
+        assert!(!sim.world.region_at(x, y).unwrap().kind.is_land());
+
+        assert!(sim.set_tile_biome(0, 0, "Swamp").is_err());
+        assert!(sim.set_tile_biome(80, 0, "Grassland").is_err());
+    }
+
+    #[test]
+    fn carnivore_kills_show_up_as_food_web_edges() {
+        let sim = run(1, SimConfig::balanced(), 99);
+        let web: serde_json::Value = serde_json::from_str(&sim.get_food_web_json()).unwrap();
+        let label = |id: u64| sim.species_registry.species[&(id as u32)].diet_label.name();
+
+        let edges: Vec<(u64, u64)> = web["species"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|s| {
+                let predator = s["id"].as_u64().unwrap();
+                s["prey"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(move |p| (predator, p["species_id"].as_u64().unwrap()))
+            })
+            .collect();
+        assert!(edges
+            .iter()
+            .any(|&(predator, prey)| label(predator) == "Carnivore" && label(prey) == "Herbivore"));
+    }
+
+    #[test]
+    fn advancing_a_fork_leaves_the_original_unchanged() {
+        let mut sim = run(2, small_config(), 20);
+        let before = serde_json::to_string(&sim).unwrap();
+
+        let mut fork = sim.fork();
+        for _ in 0..50 {
+            fork.tick();
+        }
+        assert_eq!(sim.tick, 20);
+        assert_eq!(serde_json::to_string(&sim).unwrap(), before);
+
+        for _ in 0..50 {
+            sim.tick();
+        }
+        assert_eq!(
+            serde_json::to_string(&sim).unwrap(),
+            serde_json::to_string(&fork).unwrap()
+        );
+    }
+
+    #[test]
+    fn terrain_thumbnail_has_requested_size() {
+        let sim = Simulation::new(1, small_config());
+        for (out_w, out_h) in [(20, 10), (33, 7), (160, 80), (1, 1)] {
+            let buf = sim.get_terrain_thumbnail_rgba(out_w, out_h);
+            assert_eq!(buf.len(), (out_w * out_h * 4) as usize);
+        }
+    }
+
+    #[test]
+    fn all_ocean_world_gives_all_ocean_thumbnail() {
+        let mut sim = Simulation::new(1, small_config());
+        for cell in sim.world.terrain.iter_mut().flatten() {
+            cell.biome_presences = vec![(BiomeType::Ocean, 1.0)];
+        }
+        let [r, g, b] = BiomeType::Ocean.color();
+        let buf = sim.get_terrain_thumbnail_rgba(30, 15);
+        assert!(buf.chunks(4).all(|px| px == [r, g, b, 255]));
+    }
+
+    #[test]
+    fn validate_rejects_non_finite_climate_drift() {
+        let config = SimConfig {
+            climate_drift_magnitude: f32::NAN,
+            ..small_config()
+        };
+        assert!(matches!(
+            Simulation::try_new(1, config),
+            Err(SimError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn try_new_rejects_zero_and_oversized_worlds() {
+        for (width, height) in [
+            (0, 0),
+            (0, 40),
+            (80, 0),
+            (SimConfig::MAX_WORLD_WIDTH + 1, 40),
+            (80, SimConfig::MAX_WORLD_HEIGHT + 1),
+            (u32::MAX, u32::MAX),
+        ] {
+            let config = SimConfig {
+                world_width: width,
+                world_height: height,
+                ..SimConfig::default()
+            };
+            assert!(matches!(
+                Simulation::try_new(1, config),
+                Err(SimError::InvalidDimensions { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn terrain_thumbnail_size_is_capped() {
+        let sim = Simulation::new(1, small_config());
+        let buf = sim.get_terrain_thumbnail_rgba(u32::MAX, 10);
+        assert_eq!(buf.len(), SimConfig::MAX_WORLD_WIDTH as usize * 10 * 4);
+        assert!(sim.get_terrain_thumbnail_rgba(0, 0).is_empty());
+    }
+
+    #[test]
+    fn crowded_tile_produces_fewer_offspring() {
+        let offspring = |juveniles: usize| {
+            let mut sim = Simulation::new(1, small_config());
+            let (x, y) = sim.world.habitable_tiles()[0];
+            sim.set_tile_biome(x as u32, y as u32, "Grassland").unwrap();
+
+            let mut template = sim
+                .creatures
+                .iter()
+                .find(|c| c.phenotype.is_herbivore())
+                .unwrap()
+                .clone();
+            template.phenotype.diet = 0.0;
+            template.x = x;
+            template.y = y;
+            template.target_x = Some(x);
+            template.target_y = Some(y);
+            template.reproduction_cooldown = 0;
+
+            let mut creatures = Vec::new();
+            for i in 0..4 {
+                let mut adult = template.clone();
+                adult.id = i;
+                adult.age = 20;
+                adult.energy = adult.phenotype.fertility_threshold * 10.0;
+                creatures.push(adult);
+            }
+            for i in 0..juveniles {
+                let mut juvenile = template.clone();
+                juvenile.id = 100 + i as u64;
+                juvenile.age = 0;
+                creatures.push(juvenile);
+            }
+            sim.creatures = creatures;
+            sim.next_creature_id = 1000;
+            sim.tick();
+            sim.creatures.iter().filter(|c| c.id >= 1000).count()
+        };
+
+        let sparse = offspring(0);
+        let crowded = offspring(40);
+        assert!(sparse > 0);
+        assert!(crowded < sparse, "crowded {} vs sparse {}", crowded, sparse);
+    }
+
+    /// Hash of the full serialized state. Iteration in the tick only goes through
+    /// ordered maps and vectors, so equal seeds give equal hashes in any process;
+    /// across targets, float rounding in the math library can still differ.
+    fn state_hash(sim: &Simulation) -> u64 {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(sim).unwrap().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn repeated_runs_reach_the_same_end_state() {
+        let first = state_hash(&run(9, small_config(), 1000));
+        for _ in 0..2 {
+            assert_eq!(state_hash(&run(9, small_config(), 1000)), first);
+        }
+        assert_ne!(state_hash(&run(10, small_config(), 1000)), first);
+    }
+
+    fn region_coords(json: &str) -> Vec<(u64, u64)> {
+        let region: serde_json::Value = serde_json::from_str(json).unwrap();
+        region["tiles"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| (t["x"].as_u64().unwrap(), t["y"].as_u64().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn world_region_returns_the_requested_tiles() {
+        let sim = Simulation::new(1, small_config());
+        let coords = region_coords(&sim.get_world_region_json(10, 5, 3, 2));
+        assert_eq!(
+            coords,
+            vec![(10, 5), (11, 5), (12, 5), (10, 6), (11, 6), (12, 6)]
+        );
+
+        let region: serde_json::Value =
+            serde_json::from_str(&sim.get_world_region_json(10, 5, 3, 2)).unwrap();
+        let tile = &region["tiles"][4];
+        let cell = &sim.world.terrain[6][11];
+        assert_eq!(tile["biome"], cell.dominant_biome().name());
+        assert_eq!(tile["region_id"], cell.region_id);
+    }
+
+    #[test]
+    fn world_region_wraps_around_the_east_edge() {
+        let sim = Simulation::new(1, small_config());
+        let coords = region_coords(&sim.get_world_region_json(78, 0, 4, 1));
+        assert_eq!(coords, vec![(78, 0), (79, 0), (0, 0), (1, 0)]);
+
+        // Past the last row, rows are clamped and x0 wraps
+        let region: serde_json::Value =
+            serde_json::from_str(&sim.get_world_region_json(160, 38, 2, 10)).unwrap();
+        assert_eq!(region["x0"], 0);
+        assert_eq!(region["height"], 2);
+        assert_eq!(region["tiles"].as_array().unwrap().len(), 4);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::genetics::Genome;
+use crate::genetics::{Genome, Phenotype};
 use rand::{rngs::SmallRng, Rng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Tracks species information and handles speciation events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,6 +120,17 @@ fn generate_species_color(rng: &mut SmallRng) -> [u8; 3] {
     hsv_to_rgb(hue, sat, val)
 }
 
+/// Render color from a creature's live traits: diet sets hue (green herbivores
+/// to red carnivores), body size sets brightness and camouflage dulls saturation.
+pub fn phenotype_color(phenotype: &Phenotype) -> [u8; 3] {
+    let hue = 120.0 * (1.0 - phenotype.diet.clamp(0.0, 1.0));
+    let size = ((phenotype.body_size - 0.2) / 4.8).clamp(0.0, 1.0);
+    let sat = 1.0 - phenotype.camouflage.clamp(0.0, 1.0) * 0.7;
+    let val = 0.4 + size * 0.6;
+
+    hsv_to_rgb(hue, sat, val)
+}
+
 fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [u8; 3] {
     let c = v * s;
     let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
@@ -146,18 +157,45 @@ fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [u8; 3] {
     ]
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpeciesEventKind {
+    Origination,
+    Extinction,
+}
+
+/// A timestamped change in the set of living species.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesEvent {
+    pub tick: u64,
+    pub kind: SpeciesEventKind,
+    pub species_id: u32,
+    pub ancestor_id: Option<u32>,
+}
+
 /// Manages all species in the simulation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeciesRegistry {
-    pub species: HashMap<u32, Species>,
+    /// Species by id. Ordered so that iteration, and anything derived from it,
+    /// is the same on every run with the same seed.
+    pub species: BTreeMap<u32, Species>,
     pub next_id: u32,
+    /// Originations and extinctions in the order they happened.
+    #[serde(default)]
+    pub events: Vec<SpeciesEvent>,
+}
+
+impl Default for SpeciesRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SpeciesRegistry {
     pub fn new() -> Self {
         Self {
-            species: HashMap::new(),
+            species: BTreeMap::new(),
             next_id: 1,
+            events: Vec::new(),
         }
     }
 
@@ -172,6 +210,12 @@ impl SpeciesRegistry {
         self.next_id += 1;
         let species = Species::new(id, ancestor_id, representative_genome, tick, rng);
         self.species.insert(id, species);
+        self.events.push(SpeciesEvent {
+            tick,
+            kind: SpeciesEventKind::Origination,
+            species_id: id,
+            ancestor_id,
+        });
         id
     }
 
@@ -186,16 +230,27 @@ impl SpeciesRegistry {
         }
     }
 
+    /// Count a creature leaving a species and log an extinction when none are left.
+    ///
+    /// Speciation also calls this when it moves a creature to a daughter species,
+    /// so a species whose members all move to daughters is logged as an Extinction.
     pub fn record_death(&mut self, species_id: u32, tick: u64) {
         if let Some(sp) = self.species.get_mut(&species_id) {
             sp.population = sp.population.saturating_sub(1);
             sp.total_died += 1;
             if sp.population == 0 && sp.extinct_tick.is_none() {
                 sp.extinct_tick = Some(tick);
+                self.events.push(SpeciesEvent {
+                    tick,
+                    kind: SpeciesEventKind::Extinction,
+                    species_id,
+                    ancestor_id: sp.ancestor_id,
+                });
             }
         }
     }
 
+    /// Species that are not extinct, in id order.
     pub fn living_species(&self) -> Vec<&Species> {
         self.species.values().filter(|s| !s.is_extinct()).collect()
     }
@@ -219,3 +274,54 @@ impl SpeciesRegistry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn last_death_records_one_extinction() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut registry = SpeciesRegistry::new();
+        let id = registry.create_species(None, Genome::random(&mut rng), 0, &mut rng);
+        registry.record_birth(id, 0);
+        registry.record_birth(id, 0);
+
+        registry.record_death(id, 5);
+        registry.record_death(id, 9);
+        registry.record_death(id, 12);
+
+        let extinctions: Vec<&SpeciesEvent> = registry
+            .events
+            .iter()
+            .filter(|e| e.kind == SpeciesEventKind::Extinction)
+            .collect();
+        assert_eq!(extinctions.len(), 1);
+        assert_eq!(extinctions[0].species_id, id);
+        assert_eq!(extinctions[0].tick, 9);
+        assert_eq!(registry.species[&id].extinct_tick, Some(9));
+    }
+
+    #[test]
+    fn phenotype_color_hue_follows_diet() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut herbivore = Phenotype::from_genome(&Genome::random(&mut rng));
+        herbivore.diet = 0.0;
+        let mut carnivore = herbivore.clone();
+        carnivore.diet = 1.0;
+
+        let [r, g, _] = phenotype_color(&herbivore);
+        assert!(
+            g > r,
+            "herbivore color is not green: {:?}",
+            phenotype_color(&herbivore)
+        );
+        let [r, g, _] = phenotype_color(&carnivore);
+        assert!(
+            r > g,
+            "carnivore color is not red: {:?}",
+            phenotype_color(&carnivore)
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use wasm_bindgen::prelude::*;
-use crate::simulation::{SimConfig, Simulation};
+use crate::{
+    simulation::{SimConfig, Simulation},
+    world::WorldGenConfig,
+};
 
 /// WASM-exposed simulation handle.
 #[wasm_bindgen]
@@ -17,15 +20,80 @@ impl WasmSimulation {
         }
     }
 
+    /// Create a simulation with the balanced ecology preset, where herbivores and
+    /// carnivores coexist over long runs.
+    pub fn new_balanced(seed: u32) -> WasmSimulation {
+        WasmSimulation {
+            sim: Simulation::new(seed, SimConfig::balanced()),
+        }
+    }
+
     /// Create a simulation with custom dimensions.
-    pub fn new_with_size(seed: u32, width: u32, height: u32) -> WasmSimulation {
+    pub fn new_with_size(seed: u32, width: u32, height: u32) -> Result<WasmSimulation, JsValue> {
+        let config = SimConfig {
+            world_width: width,
+            world_height: height,
+            ..SimConfig::default()
+        };
+        Self::with_config(seed, config)
+    }
+
+    /// Create a simulation with custom dimensions and world generation parameters.
+    /// Errors if a dimension is 0 or larger than 2048x1024, `continent_count` is above
+    /// 64, or a float parameter is not finite.
+    pub fn new_with_world_config(
+        seed: u32,
+        width: u32,
+        height: u32,
+        sea_level: f32,
+        continent_count: u32,
+        mountain_density: f32,
+        temperature_offset: f32,
+    ) -> Result<WasmSimulation, JsValue> {
         let config = SimConfig {
             world_width: width,
             world_height: height,
+            world_gen: WorldGenConfig {
+                sea_level,
+                continent_count,
+                mountain_density,
+                temperature_offset,
+                ..WorldGenConfig::default()
+            },
             ..SimConfig::default()
         };
+        Self::with_config(seed, config)
+    }
+
+    /// Create a simulation with custom dimensions and predator-prey balance parameters.
+    /// Each parameter is a multiplier where 1.0 matches the default behaviour.
+    /// Errors if a dimension is 0 or larger than 2048x1024, if a multiplier is negative or
+    /// not finite, or if the reproduction threshold is 0.
+    pub fn new_with_ecology(
+        seed: u32,
+        width: u32,
+        height: u32,
+        hunt_success_base: f32,
+        plant_energy_density: f32,
+        carnivore_metabolism_multiplier: f32,
+        reproduction_energy_threshold: f32,
+    ) -> Result<WasmSimulation, JsValue> {
+        let config = SimConfig {
+            world_width: width,
+            world_height: height,
+            hunt_success_base,
+            plant_energy_density,
+            carnivore_metabolism_multiplier,
+            reproduction_energy_threshold,
+            ..SimConfig::default()
+        };
+        Self::with_config(seed, config)
+    }
+
+    /// Branch an independent copy of this simulation for what-if runs.
+    pub fn fork(&self) -> WasmSimulation {
         WasmSimulation {
-            sim: Simulation::new(seed, config),
+            sim: self.sim.fork(),
         }
     }
 
@@ -51,17 +119,34 @@ impl WasmSimulation {
         self.sim.config.world_height
     }
 
+    /// Get a hash of the generated terrain, for checking two runs share the same world.
+    pub fn get_world_fingerprint(&self) -> u64 {
+        self.sim.world.fingerprint()
+    }
+
     /// Get terrain data as RGBA pixel buffer.
     pub fn get_terrain_rgba(&self) -> Vec<u8> {
         self.sim.get_terrain_rgba()
     }
 
+    /// Get terrain as an RGBA pixel buffer resized to `out_w` x `out_h`, for minimaps.
+    /// The output is capped at 2048x1024.
+    pub fn get_terrain_thumbnail_rgba(&self, out_w: u32, out_h: u32) -> Vec<u8> {
+        self.sim.get_terrain_thumbnail_rgba(out_w, out_h)
+    }
+
     /// Get creature positions and colors as flat float buffer.
     /// Layout: [x, y, r, g, b, size, diet, energy] per creature (8 floats each).
     pub fn get_creature_data(&self) -> Vec<f32> {
         self.sim.get_creature_data()
     }
 
+    /// Switch creature colors between species colors (default) and per-creature
+    /// phenotype colors.
+    pub fn set_color_by_phenotype(&mut self, enabled: bool) {
+        self.sim.config.color_by_phenotype = enabled;
+    }
+
     /// Get simulation statistics as JSON string.
     pub fn get_stats(&self) -> String {
         self.sim.get_stats_json()
@@ -72,11 +157,60 @@ impl WasmSimulation {
         self.sim.get_history_json()
     }
 
+    /// Get a histogram of a phenotype trait across living creatures as JSON string.
+    /// Returns an object with an `error` field for unknown trait names.
+    pub fn get_phenotype_histogram(&self, trait_name: &str, buckets: u32) -> String {
+        self.sim.get_phenotype_histogram_json(trait_name, buckets)
+    }
+
+    /// Get predator-prey relationships between living species as JSON string.
+    pub fn get_food_web(&self) -> String {
+        self.sim.get_food_web_json()
+    }
+
+    /// Get the species ancestry tree and origination/extinction events as JSON string.
+    pub fn get_phylogeny(&self) -> String {
+        self.sim.get_phylogeny_json()
+    }
+
+    /// Get a rectangle of tiles as JSON string, wrapping horizontally, for loading the
+    /// map in viewport-sized chunks.
+    pub fn get_world_region(&self, x0: u32, y0: u32, w: u32, h: u32) -> String {
+        self.sim.get_world_region_json(x0, y0, w, h)
+    }
+
+    /// Get the named oceans, seas, lakes, continents and islands as JSON string.
+    pub fn get_regions(&self) -> String {
+        self.sim.get_regions_json()
+    }
+
     /// Get info about a specific tile as JSON string.
     pub fn get_tile_info(&self, x: u32, y: u32) -> String {
         self.sim.get_tile_info_json(x, y)
     }
 
+    /// Paint a tile with a biome by name (e.g. "Grassland", "Ice Cap").
+    /// A land biome painted onto water lifts the tile, and Ocean painted onto land sinks it.
+    pub fn set_tile_biome(&mut self, x: u32, y: u32, biome: &str) -> Result<(), JsValue> {
+        self.sim.set_tile_biome(x, y, biome)?;
+        Ok(())
+    }
+
+    /// Set a tile's altitude and reclassify its biome.
+    pub fn set_tile_altitude(&mut self, x: u32, y: u32, altitude: f32) -> Result<(), JsValue> {
+        self.sim.set_tile_altitude(x, y, altitude)?;
+        Ok(())
+    }
+
+    /// Change the per-gene mutation rate and magnitude for future offspring.
+    /// Errors unless both values lie in [0, 1].
+    pub fn set_mutation(&mut self, rate: f32, magnitude: f32) -> Result<(), JsValue> {
+        SimConfig::validate_mutation(rate, magnitude)?;
+        self.sim.config.mutation_rate = rate;
+        self.sim.config.mutation_magnitude = magnitude;
+        Ok(())
+    }
+
     /// Get the total number of living creatures.
     pub fn get_population(&self) -> u32 {
         self.sim.creatures.iter().filter(|c| c.alive).count() as u32
@@ -87,3 +221,11 @@ impl WasmSimulation {
         self.sim.species_registry.living_species().len() as u32
     }
 }
+
+impl WasmSimulation {
+    /// Build a handle from a config, reporting invalid world dimensions to JS.
+    fn with_config(seed: u32, config: SimConfig) -> Result<WasmSimulation, JsValue> {
+        let sim = Simulation::try_new(seed, config)?;
+        Ok(WasmSimulation { sim })
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     biome::{BiomeStats, BiomeType},
+    error::SimError,
     math_util::{cartesian_coordinates, mix_values, random_point_in_sphere, repeat, Vec3},
     perlin,
+    region::{generate_region_name, Region, RegionKind},
 };
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
@@ -13,6 +15,12 @@ pub struct TerrainCell {
     pub rainfall: f32,
     pub temperature: f32,
     pub biome_presences: Vec<(BiomeType, f32)>,
+    /// Id of the water body or landmass this tile belongs to (see `World::regions`).
+    #[serde(default)]
+    pub region_id: u32,
+    /// Set when the biome was painted by hand, so climate shifts leave it alone.
+    #[serde(default)]
+    pub painted: bool,
 }
 
 impl Default for TerrainCell {
@@ -22,6 +30,8 @@ impl Default for TerrainCell {
             rainfall: 0.0,
             temperature: 0.0,
             biome_presences: Vec::new(),
+            region_id: 0,
+            painted: false,
         }
     }
 }
@@ -41,12 +51,73 @@ impl TerrainCell {
     }
 }
 
+/// Tunable parameters for world generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldGenConfig {
+    /// Altitude treated as the waterline. Raising it floods low land.
+    pub sea_level: f32,
+    /// Number of continent seeds. 1 yields a single supercontinent.
+    pub continent_count: u32,
+    /// Multiplier on the mountain range contribution to altitude.
+    pub mountain_density: f32,
+    /// Flat offset applied to every tile's temperature.
+    pub temperature_offset: f32,
+    /// Land masses with fewer tiles than this are flooded after generation.
+    /// 0 keeps every island.
+    pub min_island_size: u32,
+}
+
+impl WorldGenConfig {
+    /// Largest accepted `continent_count`; more seeds only slow generation down.
+    pub const MAX_CONTINENT_COUNT: u32 = 64;
+
+    /// Check that the parameters can produce a world.
+    pub fn validate(&self) -> Result<(), SimError> {
+        if self.continent_count == 0 || self.continent_count > Self::MAX_CONTINENT_COUNT {
+            return Err(SimError::InvalidArgument(format!(
+                "continent_count {} must be between 1 and {}",
+                self.continent_count,
+                Self::MAX_CONTINENT_COUNT
+            )));
+        }
+        for (name, value) in [
+            ("sea_level", self.sea_level),
+            ("mountain_density", self.mountain_density),
+            ("temperature_offset", self.temperature_offset),
+        ] {
+            if !value.is_finite() {
+                return Err(SimError::InvalidArgument(format!(
+                    "{} must be finite",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for WorldGenConfig {
+    fn default() -> Self {
+        Self {
+            sea_level: 0.0,
+            continent_count: World::NUM_CONTINENTS as u32,
+            mountain_density: 1.0,
+            temperature_offset: 0.0,
+            min_island_size: 4,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct World {
     pub width: u32,
     pub height: u32,
     pub seed: u32,
     pub terrain: Vec<Vec<TerrainCell>>,
+    /// Named oceans, seas, lakes, continents and islands, indexed by region id.
+    #[serde(default)]
+    pub regions: Vec<Region>,
     continent_offsets: Vec<[f32; 2]>,
     continent_sizes: Vec<[f32; 2]>,
 }
@@ -68,27 +139,47 @@ impl World {
     const TEMPERATURE_ALTITUDE_FACTOR: f32 = 2.05;
     const CONTINENT_MAX_SIZE_FACTOR: f32 = 8.7;
     const CONTINENT_MIN_SIZE_FACTOR: f32 = 5.7;
+    /// Mixed into the world seed so region names don't reuse the terrain RNG stream.
+    const REGION_NAME_SEED: u64 = 0x5245_4749_4f4e;
+    const REGION_NAME_RETRIES: usize = 8;
+    /// Altitude given to tiles that coastline cleanup or painting turns into land or water.
+    const COASTLINE_FILL_ALTITUDE: f32 = 10.0;
 
     pub fn generate(width: u32, height: u32, seed: u32) -> Self {
+        Self::generate_with_config(width, height, seed, &WorldGenConfig::default())
+    }
+
+    pub fn generate_with_config(
+        width: u32,
+        height: u32,
+        seed: u32,
+        config: &WorldGenConfig,
+    ) -> Self {
         let mut rng = SmallRng::seed_from_u64(seed as u64);
         let terrain = vec![vec![TerrainCell::default(); width as usize]; height as usize];
-        let continent_offsets = vec![[0.0, 0.0]; Self::NUM_CONTINENTS];
-        let continent_sizes = vec![[0.0, 0.0]; Self::NUM_CONTINENTS];
+        let continent_count = config
+            .continent_count
+            .min(WorldGenConfig::MAX_CONTINENT_COUNT);
+        let continent_offsets = vec![[0.0, 0.0]; continent_count as usize];
+        let continent_sizes = vec![[0.0, 0.0]; continent_count as usize];
 
         let mut world = World {
             width,
             height,
             seed,
             terrain,
+            regions: Vec::new(),
             continent_offsets,
             continent_sizes,
         };
 
         world.generate_continents(&mut rng);
-        world.generate_altitude(&mut rng);
+        world.generate_altitude(&mut rng, config.mountain_density, config.sea_level);
+        world.cleanup_coastlines(config.min_island_size as usize);
         world.generate_rainfall(&mut rng);
-        world.generate_temperature(&mut rng);
+        world.generate_temperature(&mut rng, config.temperature_offset);
         world.generate_biomes();
+        world.label_regions();
 
         world
     }
@@ -104,7 +195,7 @@ impl World {
         let mut prev_y =
             rng.gen_range(height / LATITUDE_FACTOR..height * (LATITUDE_FACTOR - 1.0) / LATITUDE_FACTOR);
 
-        for i in 0..Self::NUM_CONTINENTS {
+        for i in 0..self.continent_offsets.len() {
             let width_offset: f32 = rng.gen_range(0.0..6.0);
 
             self.continent_offsets[i] = [prev_x, prev_y];
@@ -161,7 +252,7 @@ impl World {
     fn continent_modifier(&self, x: usize, y: usize) -> f32 {
         let mut max_value: f32 = 0.0;
 
-        for i in 0..Self::NUM_CONTINENTS {
+        for i in 0..self.continent_offsets.len() {
             let distance = self.continent_distance(i, x, y);
             let value = (1.0 - distance / self.width as f32).clamp(0.0, 1.0);
 
@@ -179,6 +270,162 @@ impl World {
         max_value
     }
 
+    /// The 8 neighbours of a tile, wrapping horizontally and clamped vertically.
+    fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let width = self.width as i32;
+        let height = self.height as i32;
+        let mut result = Vec::with_capacity(8);
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let ny = y as i32 + dy;
+                if ny < 0 || ny >= height {
+                    continue;
+                }
+                let nx = (x as i32 + dx).rem_euclid(width);
+                result.push((nx as usize, ny as usize));
+            }
+        }
+
+        result
+    }
+
+    /// Smooth jagged coasts and flood land masses smaller than `min_island_size`.
+    ///
+    /// Tiles where at least 6 of 8 neighbours are on the other side of the
+    /// waterline are flipped, then connected land components are flooded if
+    /// they are too small. Only altitude is changed, so this must run before
+    /// rainfall, temperature and biomes are derived.
+    pub fn cleanup_coastlines(&mut self, min_island_size: usize) {
+        let width = self.width as usize;
+        let height = self.height as usize;
+
+        // Majority-vote smoothing pass
+        let mut flips = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let is_land = self.terrain[y][x].is_land();
+                let neighbors = self.neighbors(x, y);
+                let opposite = neighbors
+                    .iter()
+                    .filter(|&&(nx, ny)| self.terrain[ny][nx].is_land() != is_land)
+                    .count();
+                if neighbors.len() == 8 && opposite >= 6 {
+                    flips.push((x, y, is_land));
+                }
+            }
+        }
+        for (x, y, was_land) in flips {
+            self.terrain[y][x].altitude = if was_land {
+                -Self::COASTLINE_FILL_ALTITUDE
+            } else {
+                Self::COASTLINE_FILL_ALTITUDE
+            };
+        }
+
+        if min_island_size <= 1 {
+            return;
+        }
+
+        // Flood fill land components and sink the small ones
+        let mut visited = vec![vec![false; width]; height];
+        for y in 0..height {
+            for x in 0..width {
+                if visited[y][x] || !self.terrain[y][x].is_land() {
+                    continue;
+                }
+
+                let mut component = Vec::new();
+                let mut stack = vec![(x, y)];
+                visited[y][x] = true;
+                while let Some((cx, cy)) = stack.pop() {
+                    component.push((cx, cy));
+                    for (nx, ny) in self.neighbors(cx, cy) {
+                        if !visited[ny][nx] && self.terrain[ny][nx].is_land() {
+                            visited[ny][nx] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+
+                if component.len() < min_island_size {
+                    for (cx, cy) in component {
+                        self.terrain[cy][cx].altitude = -Self::COASTLINE_FILL_ALTITUDE;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Flood fill connected water bodies and landmasses into named regions.
+    ///
+    /// Region ids are assigned in scan order starting at 0 and stored on each
+    /// tile's `region_id`. Each name is drawn from an RNG seeded by the world seed
+    /// and the region's first tile in scan order, so relabeling after an edit only
+    /// renames the regions the edit touched.
+    pub fn label_regions(&mut self) {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let total_tiles = width * height;
+        let mut visited = vec![vec![false; width]; height];
+        self.regions.clear();
+
+        for y in 0..height {
+            for x in 0..width {
+                if visited[y][x] {
+                    continue;
+                }
+
+                let is_land = self.terrain[y][x].is_land();
+                let id = self.regions.len() as u32;
+                let mut size = 0;
+                let mut stack = vec![(x, y)];
+                visited[y][x] = true;
+                while let Some((cx, cy)) = stack.pop() {
+                    self.terrain[cy][cx].region_id = id;
+                    size += 1;
+                    for (nx, ny) in self.neighbors(cx, cy) {
+                        if !visited[ny][nx] && self.terrain[ny][nx].is_land() == is_land {
+                            visited[ny][nx] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+
+                let kind = RegionKind::classify(is_land, size, total_tiles);
+                let first_tile = (y * width + x) as u64;
+                let mut rng = SmallRng::seed_from_u64(
+                    self.seed as u64
+                        ^ Self::REGION_NAME_SEED
+                        ^ first_tile.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                );
+                // Re-roll a few times to avoid giving two regions the same name
+                let mut name = generate_region_name(kind, &mut rng);
+                for _ in 0..Self::REGION_NAME_RETRIES {
+                    if !self.regions.iter().any(|r| r.name == name) {
+                        break;
+                    }
+                    name = generate_region_name(kind, &mut rng);
+                }
+                self.regions.push(Region {
+                    id,
+                    kind,
+                    name,
+                    size: size as u32,
+                });
+            }
+        }
+    }
+
+    /// The region a tile belongs to, if the tile is in bounds and labeled.
+    pub fn region_at(&self, x: usize, y: usize) -> Option<&Region> {
+        let cell = self.terrain.get(y)?.get(x)?;
+        self.regions.get(cell.region_id as usize)
+    }
+
     fn random_offset_vector(rng: &mut SmallRng) -> Vec3 {
         random_point_in_sphere(rng, 1000.0)
     }
@@ -190,12 +437,12 @@ impl World {
 
     fn mountain_range_noise(noise: f32, width_factor: f32) -> f32 {
         let noise = noise * 2.0 - 1.0;
-        let v1 = -(noise * width_factor + 1.0).powi(2).exp().copysign(1.0);
+        let v1 = -(-(noise * width_factor + 1.0).powi(2)).exp();
         let v2 = (-(noise * width_factor - 1.0).powi(2)).exp();
         (v1 + v2 + 1.0) / 2.0
     }
 
-    fn generate_altitude(&mut self, rng: &mut SmallRng) {
+    fn generate_altitude(&mut self, rng: &mut SmallRng, mountain_density: f32, sea_level: f32) {
         const R1: f32 = 0.75;
         const R2: f32 = 8.0;
         const R3: f32 = 4.0;
@@ -258,10 +505,12 @@ impl World {
                 vc = mix_values(vc, v7, 0.01);
 
                 let vb = mix_values(va, va * 0.02 + 0.49, va - (2.0 * vc - 1.0).max(0.0));
-                let vd = mix_values(vb, vc, 0.225 * v8);
+                let vd = mix_values(vb, vc, (0.225 * v8 * mountain_density).min(1.0));
 
-                self.terrain[y][x].altitude =
-                    Self::MIN_ALTITUDE + (vd * Self::ALTITUDE_SPAN);
+                // Altitudes are stored relative to the sea level, so land stays `altitude > 0`.
+                self.terrain[y][x].altitude = (Self::MIN_ALTITUDE + (vd * Self::ALTITUDE_SPAN)
+                    - sea_level)
+                    .clamp(Self::MIN_ALTITUDE, Self::MAX_ALTITUDE);
             }
         }
     }
@@ -326,7 +575,7 @@ impl World {
         }
     }
 
-    fn generate_temperature(&mut self, rng: &mut SmallRng) {
+    fn generate_temperature(&mut self, rng: &mut SmallRng, temperature_offset: f32) {
         let o1 = Self::random_offset_vector(rng);
         let o2 = Self::random_offset_vector(rng);
         const R1: f32 = 2.0;
@@ -345,9 +594,10 @@ impl World {
                     (self.terrain[y][x].altitude / Self::MAX_ALTITUDE * Self::TEMPERATURE_ALTITUDE_FACTOR)
                         .max(0.0);
 
-                let temperature =
-                    ((lat_mod.sin() - alt_factor) * Self::TEMPERATURE_SPAN + Self::MIN_TEMPERATURE)
-                        .clamp(Self::MIN_TEMPERATURE, Self::MAX_TEMPERATURE);
+                let temperature = ((lat_mod.sin() - alt_factor) * Self::TEMPERATURE_SPAN
+                    + Self::MIN_TEMPERATURE
+                    + temperature_offset)
+                    .clamp(Self::MIN_TEMPERATURE, Self::MAX_TEMPERATURE);
 
                 self.terrain[y][x].temperature = temperature;
             }
@@ -357,25 +607,28 @@ impl World {
     fn generate_biomes(&mut self) {
         for y in 0..self.height as usize {
             for x in 0..self.width as usize {
-                let cell = &self.terrain[y][x];
-                let mut total = 0.0;
-                let mut presences = Vec::new();
-
-                for biome_type in BiomeType::ALL {
-                    let stats: BiomeStats = (*biome_type).into();
-                    let presence = Self::biome_presence(cell, &stats);
-                    if presence > 0.0 {
-                        presences.push((*biome_type, presence));
-                        total += presence;
-                    }
-                }
+                self.terrain[y][x].biome_presences = Self::classify_biomes(&self.terrain[y][x]);
+            }
+        }
+    }
 
-                self.terrain[y][x].biome_presences = presences
-                    .into_iter()
-                    .map(|(bt, p)| (bt, p / total))
-                    .collect();
+    fn classify_biomes(cell: &TerrainCell) -> Vec<(BiomeType, f32)> {
+        let mut total = 0.0;
+        let mut presences = Vec::new();
+
+        for biome_type in BiomeType::ALL {
+            let stats: BiomeStats = (*biome_type).into();
+            let presence = Self::biome_presence(cell, &stats);
+            if presence > 0.0 {
+                presences.push((*biome_type, presence));
+                total += presence;
             }
         }
+
+        presences
+            .into_iter()
+            .map(|(bt, p)| (bt, p / total))
+            .collect()
     }
 
     fn biome_presence(cell: &TerrainCell, biome: &BiomeStats) -> f32 {
@@ -425,6 +678,102 @@ impl World {
         presence
     }
 
+    /// Shift every tile's temperature and rainfall and reclassify biomes, except on
+    /// painted tiles. Returns the number of tiles whose dominant biome changed.
+    pub fn apply_climate_shift(&mut self, delta_temp: f32, delta_rain: f32) -> usize {
+        let previous: Vec<BiomeType> = self
+            .terrain
+            .iter()
+            .flatten()
+            .map(TerrainCell::dominant_biome)
+            .collect();
+
+        for cell in self.terrain.iter_mut().flatten() {
+            cell.temperature =
+                (cell.temperature + delta_temp).clamp(Self::MIN_TEMPERATURE, Self::MAX_TEMPERATURE);
+            cell.rainfall =
+                (cell.rainfall + delta_rain).clamp(Self::MIN_RAINFALL, Self::MAX_RAINFALL);
+            if !cell.painted {
+                cell.biome_presences = Self::classify_biomes(cell);
+            }
+        }
+
+        self.terrain
+            .iter()
+            .flatten()
+            .zip(previous)
+            .filter(|(cell, before)| cell.dominant_biome() != *before)
+            .count()
+    }
+
+    /// Force a tile to a single biome and mark it painted. Painting a land biome onto
+    /// water, or Ocean onto land, moves the tile just across the waterline.
+    /// Returns false if the tile is out of bounds.
+    pub fn set_tile_biome(&mut self, x: usize, y: usize, biome: BiomeType) -> bool {
+        match self.terrain.get_mut(y).and_then(|row| row.get_mut(x)) {
+            Some(cell) => {
+                let stats = BiomeStats::from(biome);
+                if stats.min_altitude >= 0.0 && !cell.is_land() {
+                    cell.altitude = Self::COASTLINE_FILL_ALTITUDE;
+                } else if stats.max_altitude <= 0.0 && cell.is_land() {
+                    cell.altitude = -Self::COASTLINE_FILL_ALTITUDE;
+                }
+                cell.biome_presences = vec![(biome, 1.0)];
+                cell.painted = true;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Set a tile's altitude and reclassify its biome, clearing any painted biome.
+    /// Returns false if the tile is out of bounds.
+    pub fn set_tile_altitude(&mut self, x: usize, y: usize, altitude: f32) -> bool {
+        match self.terrain.get_mut(y).and_then(|row| row.get_mut(x)) {
+            Some(cell) => {
+                cell.altitude = altitude.clamp(Self::MIN_ALTITUDE, Self::MAX_ALTITUDE);
+                cell.biome_presences = Self::classify_biomes(cell);
+                cell.painted = false;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Hash of the world's dimensions, altitudes and dominant biomes.
+    ///
+    /// Generation is deterministic, so the same (width, height, seed, config)
+    /// always produces the same fingerprint with a given build. Builds for
+    /// different targets can disagree if their floating point math libraries
+    /// round differently. The hash is FNV-1a, so it does not depend on the
+    /// standard library's hasher.
+    pub fn fingerprint(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        write(&self.width.to_le_bytes());
+        write(&self.height.to_le_bytes());
+        for cell in self.terrain.iter().flatten() {
+            write(&cell.altitude.to_bits().to_le_bytes());
+            let biome = cell.dominant_biome();
+            let biome_index = BiomeType::ALL
+                .iter()
+                .position(|bt| *bt == biome)
+                .unwrap_or(0);
+            write(&[biome_index as u8]);
+        }
+
+        hash
+    }
+
     /// Get habitable land tiles as (x, y) coordinates.
     pub fn habitable_tiles(&self) -> Vec<(usize, usize)> {
         let mut tiles = Vec::new();
@@ -439,3 +788,231 @@ impl World {
         tiles
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sizes of the connected land components, largest first.
+    fn land_component_sizes(world: &World) -> Vec<usize> {
+        let width = world.width as usize;
+        let height = world.height as usize;
+        let mut visited = vec![vec![false; width]; height];
+        let mut sizes = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if visited[y][x] || !world.terrain[y][x].is_land() {
+                    continue;
+                }
+                let mut size = 0;
+                let mut stack = vec![(x, y)];
+                visited[y][x] = true;
+                while let Some((cx, cy)) = stack.pop() {
+                    size += 1;
+                    for (nx, ny) in world.neighbors(cx, cy) {
+                        if !visited[ny][nx] && world.terrain[ny][nx].is_land() {
+                            visited[ny][nx] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+                sizes.push(size);
+            }
+        }
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    #[test]
+    fn single_continent_is_one_large_landmass() {
+        let config = WorldGenConfig {
+            continent_count: 1,
+            ..WorldGenConfig::default()
+        };
+        for seed in 1..=5 {
+            let world = World::generate_with_config(200, 100, seed, &config);
+            let sizes = land_component_sizes(&world);
+            let land: usize = sizes.iter().sum();
+            let largest = sizes.first().copied().unwrap_or(0);
+            assert!(
+                largest >= 200,
+                "seed {}: largest landmass has {} tiles",
+                seed,
+                largest
+            );
+            assert!(
+                largest * 10 >= land * 9,
+                "seed {}: largest landmass has {} of {} land tiles",
+                seed,
+                largest,
+                land
+            );
+        }
+    }
+
+    #[test]
+    fn continent_count_is_bounded() {
+        let config = WorldGenConfig {
+            continent_count: 0,
+            ..WorldGenConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = WorldGenConfig {
+            continent_count: 200_000,
+            ..WorldGenConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let world = World::generate_with_config(40, 20, 1, &config);
+        assert_eq!(
+            world.continent_offsets.len(),
+            WorldGenConfig::MAX_CONTINENT_COUNT as usize
+        );
+    }
+
+    #[test]
+    fn large_cooling_turns_forest_into_taiga_or_tundra() {
+        let mut world = World::generate(200, 100, 1);
+        let forests: Vec<(usize, usize)> = (0..world.height as usize)
+            .flat_map(|y| (0..world.width as usize).map(move |x| (x, y)))
+            .filter(|&(x, y)| world.terrain[y][x].dominant_biome() == BiomeType::Forest)
+            .collect();
+        assert!(!forests.is_empty());
+
+        world.apply_climate_shift(-20.0, 0.0);
+        let cold = forests
+            .iter()
+            .filter(|&&(x, y)| {
+                matches!(
+                    world.terrain[y][x].dominant_biome(),
+                    BiomeType::Taiga | BiomeType::Tundra
+                )
+            })
+            .count();
+        assert!(
+            cold * 4 > forests.len(),
+            "{} of {} forest tiles cooled",
+            cold,
+            forests.len()
+        );
+        let warm = forests.iter().filter(|&&(x, y)| {
+            matches!(
+                world.terrain[y][x].dominant_biome(),
+                BiomeType::Grassland | BiomeType::Desert | BiomeType::Rainforest
+            )
+        });
+        assert_eq!(warm.count(), 0);
+    }
+
+    #[test]
+    fn climate_shift_keeps_painted_tiles() {
+        let mut world = World::generate(80, 40, 1);
+        let (x, y) = (0..world.height as usize)
+            .flat_map(|y| (0..world.width as usize).map(move |x| (x, y)))
+            .find(|&(x, y)| world.terrain[y][x].is_land())
+            .unwrap();
+        assert!(world.set_tile_biome(x, y, BiomeType::Desert));
+
+        world.apply_climate_shift(-25.0, 5000.0);
+        assert_eq!(world.terrain[y][x].dominant_biome(), BiomeType::Desert);
+
+        assert!(world.set_tile_altitude(x, y, world.terrain[y][x].altitude));
+        assert!(!world.terrain[y][x].painted);
+    }
+
+    #[test]
+    fn cleanup_leaves_no_island_below_min_size() {
+        let config = WorldGenConfig {
+            min_island_size: 10,
+            ..WorldGenConfig::default()
+        };
+        for seed in 1..=5 {
+            let world = World::generate_with_config(120, 60, seed, &config);
+            let sizes = land_component_sizes(&world);
+            let smallest = sizes.last().copied().unwrap_or(usize::MAX);
+            assert!(
+                smallest >= 10,
+                "seed {}: island of {} tiles survived",
+                seed,
+                smallest
+            );
+        }
+    }
+
+    #[test]
+    fn fingerprint_depends_only_on_generation_parameters() {
+        let config = WorldGenConfig::default();
+        let a = World::generate_with_config(120, 60, 3, &config);
+        let b = World::generate_with_config(120, 60, 3, &config);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+
+        let other_seed = World::generate_with_config(120, 60, 4, &config);
+        assert_ne!(a.fingerprint(), other_seed.fingerprint());
+    }
+
+    #[test]
+    fn label_regions_separates_landmasses_across_ocean() {
+        let mut world = World::generate(40, 20, 1);
+        for (y, row) in world.terrain.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                let west = (5..10).contains(&x);
+                let east = (25..30).contains(&x);
+                cell.altitude = if (west || east) && (5..10).contains(&y) {
+                    100.0
+                } else {
+                    -100.0
+                };
+            }
+        }
+        world.label_regions();
+
+        let west = world.terrain[5][5].region_id;
+        let east = world.terrain[5][25].region_id;
+        let ocean = world.terrain[0][0].region_id;
+        assert_ne!(west, east);
+        assert_ne!(west, ocean);
+        assert_ne!(east, ocean);
+        for y in 5..10 {
+            for x in 5..10 {
+                assert_eq!(world.terrain[y][x].region_id, west);
+                assert_eq!(world.terrain[y][x + 20].region_id, east);
+            }
+        }
+        assert_eq!(world.regions.len(), 3);
+        assert_eq!(world.regions[west as usize].size, 25);
+        assert!(world.regions[west as usize].kind.is_land());
+        assert!(!world.regions[ocean as usize].kind.is_land());
+
+        let world = World::generate(120, 60, 2);
+        let land_regions = world.regions.iter().filter(|r| r.kind.is_land()).count();
+        assert_eq!(land_regions, land_component_sizes(&world).len());
+    }
+
+    #[test]
+    fn editing_a_tile_keeps_the_names_of_unaffected_regions() {
+        let mut world = World::generate(40, 20, 1);
+        for (y, row) in world.terrain.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                let west = (5..10).contains(&x);
+                let east = (25..30).contains(&x);
+                cell.altitude = if (west || east) && (5..10).contains(&y) {
+                    100.0
+                } else {
+                    -100.0
+                };
+            }
+        }
+        world.label_regions();
+        let names = |world: &World| {
+            [(0, 0), (5, 5), (25, 5)].map(|(x, y)| world.region_at(x, y).unwrap().name.clone())
+        };
+        let before = names(&world);
+
+        // A new island early in scan order shifts every later region id
+        world.terrain[2][2].altitude = 100.0;
+        world.label_regions();
+        assert_eq!(world.regions.len(), 4);
+        assert_eq!(names(&world), before);
+    }
+}
@@ -0,0 +1,24 @@
+use evolution_sim::simulation::{SimConfig, Simulation};
+
+#[test]
+fn balanced_preset_keeps_herbivores_and_carnivores_for_2000_ticks() {
+    for seed in 1..=3 {
+        let mut sim = Simulation::new(seed, SimConfig::balanced());
+        for _ in 0..2000 {
+            sim.tick();
+        }
+
+        let herbivores = sim
+            .creatures
+            .iter()
+            .filter(|c| c.phenotype.is_herbivore())
+            .count();
+        let carnivores = sim
+            .creatures
+            .iter()
+            .filter(|c| c.phenotype.is_carnivore())
+            .count();
+        assert!(herbivores > 0, "seed {}: herbivores died out", seed);
+        assert!(carnivores > 0, "seed {}: carnivores died out", seed);
+    }
+}
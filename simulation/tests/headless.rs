@@ -0,0 +1,35 @@
+use std::process::Command;
+
+#[test]
+fn headless_run_writes_stats_and_history_as_json() {
+    let output = Command::new(env!("CARGO_BIN_EXE_headless"))
+        .args([
+            "--width", "80", "--height", "40", "--seed", "7", "--ticks", "300",
+        ])
+        .output()
+        .expect("failed to run headless");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("output is not valid JSON");
+    assert_eq!(json["stats"]["tick"], 300);
+    assert!(json["stats"]["total_population"].is_u64());
+
+    let history = json["history"].as_array().expect("history is not an array");
+    assert_eq!(history.len(), 30);
+    assert_eq!(history.last().unwrap()["tick"], 300);
+}
+
+#[test]
+fn headless_rejects_unknown_flags() {
+    let output = Command::new(env!("CARGO_BIN_EXE_headless"))
+        .arg("--bogus")
+        .arg("1")
+        .output()
+        .expect("failed to run headless");
+    assert_eq!(output.status.code(), Some(2));
+}